@@ -0,0 +1,79 @@
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::Error;
+
+// CachedResponse is the last body a source got for a key, along with what the
+// server told us so we can ask whether it has changed since
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+// ResponseCache keeps responses on disk under the user's cache dir, so a restart
+// can show what we last knew straight away
+#[derive(Clone, Debug)]
+pub struct ResponseCache {
+    dir: Option<PathBuf>,
+}
+
+impl ResponseCache {
+    // new makes a cache for one source, e.g. ~/.cache/wicketick/cricinfo
+    pub fn new(source: &str) -> Self {
+        Self {
+            dir: dirs::cache_dir().map(|d| d.join("wicketick").join(source)),
+        }
+    }
+
+    fn path(&self, key: &str) -> Option<PathBuf> {
+        // keys end up as file names, so keep them to something sensible
+        let key: String = key
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        Some(self.dir.as_ref()?.join(format!("{}.json", key)))
+    }
+
+    // load gets a response back out of the cache, missing or broken entries are just None
+    pub fn load(&self, key: &str) -> Option<CachedResponse> {
+        let file = std::fs::File::open(self.path(key)?).ok()?;
+        serde_json::from_reader(file).ok()
+    }
+
+    // saved_at is when the response for the key was stored
+    pub fn saved_at(&self, key: &str) -> Option<SystemTime> {
+        std::fs::metadata(self.path(key)?).ok()?.modified().ok()
+    }
+
+    // newest_key is the key stored most recently, e.g. the last match we followed
+    pub fn newest_key(&self) -> Option<String> {
+        let entries = std::fs::read_dir(self.dir.as_ref()?).ok()?;
+        entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+            .max_by_key(|(modified, _)| *modified)
+            .and_then(|(_, path)| Some(path.file_stem()?.to_string_lossy().into_owned()))
+    }
+
+    pub fn store(&self, key: &str, response: &CachedResponse) -> Result<(), Error> {
+        let path = self
+            .path(key)
+            .ok_or(Error::Todo("no cache dir".to_string()))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        // write then rename, so a crash never leaves half an entry behind
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(response)?)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
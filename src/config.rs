@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::errors::Error;
+use crate::notify::Filter;
+
+// Config is what can be set in the config file, anything left out falls back to the
+// defaults. Flags on the command line win over the file.
+//
+//     source = { type = "cricinfo", match_id = "1442214" }
+//     poll_interval = 20
+//     favourite_teams = ["Worcestershire"]
+//     favourite_matches = ["1442214"]
+//     ticker = "relaxed"
+//     theme = "light"
+//
+//     [team_colours]
+//     Worcestershire = "green"
+//
+//     [keys]
+//     quit = "Q"
+//
+//     # desktop notifications, for just these events and teams
+//     [notify]
+//     events = ["wicket", "result"]
+//     teams = ["Worcestershire"]
+//
+//     [[hooks]]
+//     command = "paplay ~/howzat.ogg"
+//     events = ["wicket"]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub source: Option<SourceConfig>,
+    // seconds between polls
+    pub poll_interval: Option<u64>,
+    // team names or abbreviations. Cricinfo only lists abbreviations, so full names are
+    // matched up with them by guessing, and a nickname like Birmingham Bears for
+    // Warwickshire needs giving as listed.
+    pub favourite_teams: Vec<String>,
+    // matches for the dashboard to start with
+    pub favourite_matches: Vec<String>,
+    // matches to list the others from, as cricinfo only lists matches alongside another
+    // one. The favourite matches get tried after these.
+    pub discovery_matches: Vec<String>,
+    pub ticker: Option<TickerLayout>,
+    // classic, dark, light or mono
+    pub theme: Option<String>,
+    // accent colours for teams, by any of their names, e.g. Worcestershire = "#00843d"
+    pub team_colours: BTreeMap<String, String>,
+    pub keys: KeyBindings,
+    // desktop notifications, leave it out for none
+    pub notify: Option<Filter>,
+    pub hooks: Vec<HookConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum SourceConfig {
+    Cricinfo {
+        #[serde(default)]
+        match_id: Option<String>,
+    },
+    LocalCricinfo {
+        filename: String,
+    },
+}
+
+// HookConfig is a command to run for match events, told only about the events and
+// teams it lists, or all of them if it doesn't list any
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookConfig {
+    pub command: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub teams: Vec<String>,
+}
+
+// TickerLayout is which ticker configuration the live stream opens with
+#[derive(Debug, Clone, Copy, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum TickerLayout {
+    Minimal,
+    Relaxed,
+    Commentary,
+}
+
+// KeyBindings are the keys for everything that isn't the arrow keys, enter or escape
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    pub quit: char,
+    pub refresh: char,
+    // switches between the minimal and relaxed tickers
+    pub layout: char,
+    pub commentary: char,
+    pub details: char,
+    pub up: char,
+    pub down: char,
+    // adds and removes matches on the dashboard
+    pub add: char,
+    pub remove: char,
+    // shows the keys for the current phase
+    pub help: char,
+}
+
+impl KeyBindings {
+    // named are the bindings with the names they have in the config
+    fn named(&self) -> [(&'static str, char); 10] {
+        [
+            ("quit", self.quit),
+            ("refresh", self.refresh),
+            ("layout", self.layout),
+            ("commentary", self.commentary),
+            ("details", self.details),
+            ("up", self.up),
+            ("down", self.down),
+            ("add", self.add),
+            ("remove", self.remove),
+            ("help", self.help),
+        ]
+    }
+
+    // check rejects a key bound to two things, as only one of them would ever happen.
+    // The number keys are taken too, for picking from numbered lists.
+    pub fn check(&self) -> Result<(), Error> {
+        let named = self.named();
+        for (i, (name, key)) in named.iter().enumerate() {
+            if key.is_ascii_digit() {
+                return Err(Error::Todo(format!(
+                    "can't bind {} to {} in [keys], the number keys pick from lists",
+                    name, key
+                )));
+            }
+            if let Some((other, _)) = named[..i].iter().find(|(_, k)| k == key) {
+                return Err(Error::Todo(format!(
+                    "{} and {} are both bound to {} in [keys]",
+                    other, name, key
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: 'q',
+            refresh: 'r',
+            layout: 'l',
+            commentary: 'c',
+            details: 'd',
+            up: 'k',
+            down: 'j',
+            add: 'a',
+            remove: 'x',
+            help: '?',
+        }
+    }
+}
+
+impl Config {
+    // default_path is e.g. ~/.config/wicketick/config.toml
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("wicketick").join("config.toml"))
+    }
+
+    // load reads the config file at path, or the default one. Not having a config file
+    // is fine, but having a broken one isn't.
+    pub fn load(path: Option<&Path>) -> Result<Self, Error> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match Self::default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+        let text = std::fs::read_to_string(&path)?;
+        let config: Self = toml::from_str(&text)?;
+        config.keys.check()?;
+        Ok(config)
+    }
+}
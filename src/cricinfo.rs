@@ -1,28 +1,31 @@
 use std::fmt;
 use std::marker::PhantomData;
+use std::str::FromStr;
 
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::cache::{CachedResponse, ResponseCache};
 use crate::errors::Error;
-use crate::wicketick::{self};
-use reqwest;
+use crate::replay::{Recorder, ReplaySpeed, Session};
+use crate::wicketick::{self, MatchListing, MatchSource};
+use async_trait::async_trait;
+use reqwest::{header, StatusCode};
 use serde::{Deserialize, Deserializer};
+use tokio::sync::Mutex;
 
 // example match ids:
 // finished test match = 1385691
 // currently in progress t20 = 1410472
 pub async fn get_match_summary(match_id: String) -> Result<wicketick::SimpleSummary, Error> {
-    let body = reqwest::get(format!(
-        "https://www.espncricinfo.com/matches/engine/match/{}.json",
-        match_id
-    ))
-    .await?
-    .text()
-    .await?;
+    let body = fetch_match_json(&match_id).await?;
 
     let match_summary: Summary = serde_json::from_str(&body)?;
 
-    let wicketick = match_summary.into();
-
-    return Ok(wicketick);
+    Ok(match_summary.into())
 }
 
 pub fn load_match_summary(filename: String) -> Result<wicketick::SimpleSummary, Error> {
@@ -30,18 +33,359 @@ pub fn load_match_summary(filename: String) -> Result<wicketick::SimpleSummary,
 
     let match_summary: Summary = serde_json::from_reader(file)?;
 
-    let wicketick = match_summary.into();
+    Ok(match_summary.into())
+}
+
+// get_other_matches lists the matches cricinfo bundles alongside the given match
+pub async fn get_other_matches(match_id: String) -> Result<Vec<MatchListing>, Error> {
+    let body = fetch_match_json(&match_id).await?;
+
+    let listing: Listing = serde_json::from_str(&body)?;
 
-    return Ok(wicketick);
+    Ok(listing.into_listings())
 }
 
-fn parse_u32(bob: String) -> u32 {
-    bob.parse::<u32>()
-        .map_err(|e| {
-            eprintln!("failed to parse u32 {}", e);
-            Some(0)
+static USER_AGENT: &str = concat!("wicketick/", env!("CARGO_PKG_VERSION"));
+static REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+// client is shared by every request so connections get reused between polls
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+fn match_url(match_id: &str) -> String {
+    format!(
+        "https://www.espncricinfo.com/matches/engine/match/{}.json",
+        match_id
+    )
+}
+
+async fn fetch_match_json(match_id: &str) -> Result<String, Error> {
+    let body = client()
+        .get(match_url(match_id))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    Ok(body)
+}
+
+// fetch_match_json_since only downloads the match json if it has changed since the
+// cached response, returning None if it hasn't
+async fn fetch_match_json_since(
+    match_id: &str,
+    cached: Option<&CachedResponse>,
+) -> Result<Option<CachedResponse>, Error> {
+    let mut request = client().get(match_url(match_id));
+    if let Some(cached) = cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = request.send().await?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    let response = response.error_for_status()?;
+
+    let header_value = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v: &header::HeaderValue| v.to_str().ok())
+            .map(|v| v.to_string())
+    };
+    let etag = header_value(header::ETAG);
+    let last_modified = header_value(header::LAST_MODIFIED);
+    Ok(Some(CachedResponse {
+        etag,
+        last_modified,
+        body: response.text().await?,
+    }))
+}
+
+// Cricinfo has no endpoint listing matches, but every match json carries the scores
+// of the other matches going on around it, so we borrow those of another match when
+// we've not been given one of our own.
+
+// LastResponse is the last match json we got and what it parsed to
+struct LastResponse {
+    response: CachedResponse,
+    summary: wicketick::SimpleSummary,
+}
+
+// Cricinfo fetches live summaries from the cricinfo match engine
+#[derive(Clone)]
+pub struct Cricinfo {
+    match_id: Option<String>,
+    // matches to borrow the listing of, best first
+    discovery: Vec<String>,
+    cache: ResponseCache,
+    last: Arc<Mutex<Option<LastResponse>>>,
+    recorder: Option<Recorder>,
+}
+
+impl Cricinfo {
+    pub fn new(match_id: Option<String>) -> Self {
+        Self {
+            match_id,
+            discovery: vec![],
+            cache: ResponseCache::new("cricinfo"),
+            last: Arc::new(Mutex::new(None)),
+            recorder: None,
+        }
+    }
+
+    // discovering_from gives the matches to list the others from, e.g. favourites
+    pub fn discovering_from(mut self, match_ids: Vec<String>) -> Self {
+        self.discovery = match_ids;
+        self
+    }
+
+    // discovery_ids are the matches to try listing from, our own first, then the ones
+    // we were given, then whichever we last fetched
+    fn discovery_ids(&self) -> Vec<String> {
+        let candidates = self
+            .match_id
+            .iter()
+            .chain(&self.discovery)
+            .cloned()
+            .chain(self.cache.newest_key());
+        let mut ids: Vec<String> = vec![];
+        for id in candidates {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    // recording saves every response we get into a session for replaying later
+    pub fn recording(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    fn load_cached(&self, match_id: &str) -> Option<LastResponse> {
+        let response = self.cache.load(match_id)?;
+        let summary: Summary = serde_json::from_str(&response.body).ok()?;
+        Some(LastResponse {
+            response,
+            summary: summary.into(),
         })
-        .unwrap()
+    }
+}
+
+#[async_trait]
+impl MatchSource for Cricinfo {
+    async fn fetch_summary(&self) -> Result<wicketick::SimpleSummary, Error> {
+        let match_id = match &self.match_id {
+            Some(m_id) => m_id,
+            // Nothing to refresh
+            None => return Err(Error::NoMatchId),
+        };
+
+        let mut last = self.last.lock().await;
+        if last.is_none() {
+            *last = self.load_cached(match_id);
+        }
+
+        // a recording wants every body, not just the ones that have changed
+        let cached = match self.recorder {
+            Some(_) => None,
+            None => last.as_ref().map(|l| &l.response),
+        };
+        match fetch_match_json_since(match_id, cached).await? {
+            Some(response) => {
+                if let Some(recorder) = &self.recorder {
+                    // a gap in the recording is better than a gap in the ticker
+                    let _ = recorder.record(match_id, &response.body);
+                }
+                // not every server honours the conditional headers
+                if let Some(l) = last.as_ref() {
+                    if l.response.body == response.body {
+                        return Ok(l.summary.clone());
+                    }
+                }
+                let summary: wicketick::SimpleSummary =
+                    serde_json::from_str::<Summary>(&response.body)?.into();
+                // it's only a cache, the next poll will try again
+                let _ = self.cache.store(match_id, &response);
+                *last = Some(LastResponse {
+                    response,
+                    summary: summary.clone(),
+                });
+                Ok(summary)
+            }
+            None => match last.as_ref() {
+                Some(l) => Ok(l.summary.clone()),
+                None => Err(Error::NotModifiedUncached),
+            },
+        }
+    }
+
+    fn cached_summary(&self) -> Option<(wicketick::SimpleSummary, SystemTime)> {
+        let match_id = self.match_id.as_ref()?;
+        let saved_at = self.cache.saved_at(match_id)?;
+        // hold on to it for the first fetch, rather than parsing the body again then
+        let mut last = self.last.try_lock().ok()?;
+        if last.is_none() {
+            *last = self.load_cached(match_id);
+        }
+        Some((last.as_ref()?.summary.clone(), saved_at))
+    }
+
+    async fn list_matches(&self) -> Result<Vec<MatchListing>, Error> {
+        // an old match might have nothing around it any more, so move on from those too
+        let mut last = Err(Error::NoDiscoveryMatch);
+        for match_id in self.discovery_ids() {
+            last = get_other_matches(match_id).await;
+            if last.as_ref().is_ok_and(|listings| !listings.is_empty()) {
+                break;
+            }
+        }
+        last
+    }
+
+    fn select_match(&self, listing: &MatchListing) -> Arc<dyn MatchSource> {
+        let mut source = Cricinfo::new(Some(listing.match_id.clone()));
+        source.discovery = self.discovery.clone();
+        source.recorder = self.recorder.clone();
+        Arc::new(source)
+    }
+
+    fn should_poll(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> String {
+        "CricInfo".to_string()
+    }
+}
+
+// LocalCricinfo loads a summary from a copy of a cricinfo match json on disk
+#[derive(Clone)]
+pub struct LocalCricinfo {
+    filename: String,
+}
+
+impl LocalCricinfo {
+    pub fn new(filename: String) -> Self {
+        Self { filename }
+    }
+}
+
+#[async_trait]
+impl MatchSource for LocalCricinfo {
+    async fn fetch_summary(&self) -> Result<wicketick::SimpleSummary, Error> {
+        load_match_summary(self.filename.clone())
+    }
+
+    async fn list_matches(&self) -> Result<Vec<MatchListing>, Error> {
+        let file = std::fs::File::open(&self.filename)?;
+        let listing: Listing = serde_json::from_reader(file)?;
+        Ok(vec![listing.matchinfo.into_listing(&self.filename)])
+    }
+
+    fn select_match(&self, _listing: &MatchListing) -> Arc<dyn MatchSource> {
+        // there's only ever the one match in the file
+        Arc::new(self.clone())
+    }
+
+    // the file isn't going to change under us
+    fn should_poll(&self) -> bool {
+        false
+    }
+
+    fn name(&self) -> String {
+        format!("CricInfo (local file {})", self.filename)
+    }
+}
+
+// how often a timed replay checks whether it's time for the next response, however
+// slowly the recorded match was going
+static REPLAY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// CricinfoReplay plays back a session of cricinfo responses saved by a Recorder
+#[derive(Clone)]
+pub struct CricinfoReplay {
+    dir: String,
+    speed: ReplaySpeed,
+    // when the first fetch happened, timed replays play out from then
+    started: Arc<OnceLock<Instant>>,
+    next_step: Arc<AtomicUsize>,
+}
+
+impl CricinfoReplay {
+    pub fn new(dir: String, speed: ReplaySpeed) -> Self {
+        Self {
+            dir,
+            speed,
+            started: Arc::new(OnceLock::new()),
+            next_step: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+#[async_trait]
+impl MatchSource for CricinfoReplay {
+    async fn fetch_summary(&self) -> Result<wicketick::SimpleSummary, Error> {
+        // load it every time, so a session that's still being recorded keeps growing
+        let session = Session::load(Path::new(&self.dir))?;
+        let frame = match self.speed {
+            ReplaySpeed::Timed(factor) => {
+                let started = self.started.get_or_init(Instant::now);
+                session.frame_at(started.elapsed().mul_f64(factor))
+            }
+            ReplaySpeed::Stepped => self.next_step.fetch_add(1, Ordering::Relaxed),
+        };
+        let match_summary: Summary = serde_json::from_str(&session.read(frame)?)?;
+        Ok(match_summary.into())
+    }
+
+    async fn list_matches(&self) -> Result<Vec<MatchListing>, Error> {
+        let session = Session::load(Path::new(&self.dir))?;
+        let listing: Listing = serde_json::from_str(&session.read(0)?)?;
+        Ok(vec![listing.matchinfo.into_listing(&self.dir)])
+    }
+
+    fn select_match(&self, _listing: &MatchListing) -> Arc<dyn MatchSource> {
+        // a session only ever records the one match
+        Arc::new(self.clone())
+    }
+
+    fn should_poll(&self) -> bool {
+        true
+    }
+
+    // stepped replays move on every poll, timed ones keep up with the recording
+    fn fixed_poll_interval(&self, interval: Duration) -> Option<Duration> {
+        match self.speed {
+            ReplaySpeed::Timed(_) => Some(REPLAY_POLL_INTERVAL.min(interval)),
+            ReplaySpeed::Stepped => Some(interval),
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("CricInfo (replay of {})", self.dir)
+    }
+}
+
+// parse_u32 is 0 for anything that isn't a number, rather than failing the whole poll
+fn parse_u32(bob: String) -> u32 {
+    bob.parse::<u32>().unwrap_or_default()
 }
 
 // Layout in structs all the info from the Json they host, that we actually care about
@@ -50,8 +394,14 @@ fn parse_u32(bob: String) -> u32 {
 #[derive(Deserialize, Debug)]
 struct Summary {
     live: LiveState,
+    #[serde(rename = "match")]
+    matchinfo: MatchInfo,
     // centre: Centre,
     team: Vec<Team>,
+    #[serde(default)]
+    innings: Vec<Innings>,
+    #[serde(default)]
+    comms: Vec<Comms>,
 }
 
 impl Summary {
@@ -64,15 +414,10 @@ impl Summary {
                 }
             }
         }
-        return "Unkown".to_string();
+        "Unkown".to_string()
     }
 }
 
-#[derive(Deserialize, Debug)]
-struct LocalSummary {
-    live: LiveState,
-}
-
 // #[derive(Deserialize, Debug)]
 // struct Centre {
 //     pub batting: Vec<Batter>,
@@ -82,7 +427,6 @@ struct LocalSummary {
 #[derive(Deserialize, Debug)]
 struct Player {
     known_as: String,
-    popular_name: String,
     player_id: String,
 }
 
@@ -92,17 +436,22 @@ struct Batter {
     live_current_name: String,
     #[serde(deserialize_with = "deserialize_stringy_int")]
     runs: u32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    fours: u32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    sixes: u32,
     player_id: String,
-    team_id: u32,
 }
 
 impl Batter {
-    fn to_wicketick(self, name: &str) -> wicketick::Batter {
+    fn into_wicketick(self, name: &str) -> wicketick::Batter {
         let balls_faced = parse_u32(self.balls_faced);
         wicketick::Batter::new(
             name,
             self.runs,
             balls_faced,
+            self.fours,
+            self.sixes,
             self.live_current_name == "striker",
         )
     }
@@ -111,47 +460,257 @@ impl Batter {
 #[derive(Deserialize, Debug, Clone)]
 struct Bowler {
     overs: String,
-    live_current_name: String,
     #[serde(deserialize_with = "deserialize_stringy_int")]
     // TODO might not be needed but given batters runs needed it, probably a decent idea
     conceded: u32,
     wickets: u32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    maidens: u32,
     player_id: String,
-    team_id: u32,
 }
 
 impl Bowler {
-    fn to_wicketick(self, name: &str) -> wicketick::Bowler {
+    fn into_wicketick(self, name: &str, balls_per_over: u32) -> wicketick::Bowler {
         wicketick::Bowler::new(
             name,
-            wicketick::Overs::from_str_with_default(&self.overs),
+            wicketick::Overs::from_str_with_default(&self.overs, balls_per_over),
             self.wickets,
             self.conceded,
+            self.maidens,
         )
     }
 }
 
 #[derive(Deserialize, Debug)]
 struct LiveState {
-    pub innings: Innings,
+    // missing until the first ball is bowled
+    #[serde(default)]
+    pub innings: Option<Innings>,
+    #[serde(default)]
     pub batting: Vec<Batter>,
+    #[serde(default)]
     pub bowling: Vec<Bowler>,
-    // pub fow: Vec<FoW>,
-    // pub status: String,
+    #[serde(default)]
+    pub fow: Vec<FoW>,
+    #[serde(default)]
+    pub recent_overs: Vec<Vec<RecentBall>>,
+    #[serde(default)]
+    pub status: String,
+    // lunch, tea, stumps... empty during play
+    #[serde(default, rename = "break")]
+    pub break_name: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
+struct RecentBall {
+    // "&bull;", "W", or the runs which might be a number or a string
+    ball: serde_json::Value,
+    #[serde(default)]
+    extras: String,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    over_number: u32,
+}
+
+impl RecentBall {
+    fn to_wicketick(&self) -> wicketick::BallOutcome {
+        let token = match &self.ball {
+            serde_json::Value::String(s) => decode_html_entities(s).trim().to_string(),
+            serde_json::Value::Number(n) => n.to_string(),
+            other => other.to_string(),
+        };
+        parse_ball(&token, &self.extras)
+    }
+}
+
+// parse_ball turns cricinfo's ball token and extras code into an outcome
+fn parse_ball(token: &str, extras: &str) -> wicketick::BallOutcome {
+    use wicketick::BallOutcome;
+
+    if token.eq_ignore_ascii_case("w") {
+        return BallOutcome::Wicket;
+    }
+    if token == "\u{2022}" || token.is_empty() {
+        return match extras.is_empty() {
+            true => BallOutcome::Dot,
+            false => parse_extras(0, extras),
+        };
+    }
+    match token.parse::<u32>() {
+        Ok(runs) if extras.is_empty() => match runs {
+            0 => BallOutcome::Dot,
+            n => BallOutcome::Runs(n),
+        },
+        Ok(runs) => parse_extras(runs, extras),
+        Err(_) => BallOutcome::Other(token.to_string()),
+    }
+}
+
+fn parse_extras(runs: u32, extras: &str) -> wicketick::BallOutcome {
+    use wicketick::BallOutcome;
+
+    // cricinfo counts the penalty run of a wide in the token, sometimes it doesn't send one
+    let runs = runs.max(1);
+    match extras.trim().to_lowercase().as_str() {
+        "w" | "wd" | "wide" | "wides" => BallOutcome::Wide(runs),
+        "nb" | "noball" | "noballs" => BallOutcome::NoBall(runs),
+        "lb" | "legbye" | "legbyes" => BallOutcome::LegBye(runs),
+        "b" | "bye" | "byes" => BallOutcome::Bye(runs),
+        other => BallOutcome::Other(format!("{}{}", runs, other)),
+    }
+}
+
+// Innings is used both for live.innings and for each entry in the top level innings
+// array, only the latter has the extras breakdown.
+#[derive(Deserialize, Debug, Clone)]
 struct Innings {
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    innings_number: u32,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    batting_team_id: u32,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    bowling_team_id: u32,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
     runs: u32,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
     wickets: u32,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
     target: u32,
     overs: String,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    lead: i32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    byes: u32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    legbyes: u32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    wides: u32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    noballs: u32,
+    #[serde(default, deserialize_with = "deserialize_stringy_int")]
+    penalties: u32,
 }
 
-// #[derive(Deserialize, Debug)]
-// struct FoW {
-//     fow_order: u8,
-// }
+impl Innings {
+    fn into_wicketick(self, balls_per_over: u32) -> wicketick::Innings {
+        wicketick::Innings {
+            number: self.innings_number,
+            batting_team_id: self.batting_team_id.to_string(),
+            bowling_team_id: self.bowling_team_id.to_string(),
+            runs: self.runs,
+            wickets: self.wickets,
+            overs: wicketick::Overs::from_str_with_default(&self.overs, balls_per_over),
+            target: match self.target {
+                0 => None,
+                n => Some(n),
+            },
+            lead: self.lead,
+            extras: wicketick::Extras {
+                byes: self.byes,
+                leg_byes: self.legbyes,
+                wides: self.wides,
+                no_balls: self.noballs,
+                penalties: self.penalties,
+            },
+        }
+    }
+}
+
+// Comms is one over's worth of ball by ball commentary
+#[derive(Deserialize, Debug)]
+struct Comms {
+    #[serde(default)]
+    ball: Vec<CommsBall>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CommsBall {
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    innings_number: u32,
+    overs_actual: String,
+    overs_unique: String,
+    #[serde(default)]
+    players: String,
+    #[serde(default)]
+    event: String,
+    #[serde(default)]
+    dismissal: String,
+    #[serde(default)]
+    text: String,
+}
+
+impl CommsBall {
+    fn into_wicketick(self) -> wicketick::CommentaryBall {
+        wicketick::CommentaryBall {
+            innings: self.innings_number,
+            overs_actual: self.overs_actual,
+            overs_unique: self.overs_unique,
+            players: decode_html_entities(&self.players),
+            event: decode_html_entities(&self.event),
+            dismissal: decode_html_entities(&self.dismissal),
+            text: decode_html_entities(&self.text),
+        }
+    }
+}
+
+// FoW covers both the current partnership and the wickets that have fallen,
+// told apart by live_current_name
+#[derive(Deserialize, Debug, Clone)]
+struct FoW {
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    fow_order: u32,
+    fow_overs: String,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    fow_runs: u32,
+    #[serde(default)]
+    live_current_name: Option<String>,
+    #[serde(default)]
+    out_player: OutPlayer,
+    partnership_overs: String,
+    #[serde(deserialize_with = "deserialize_stringy_float")]
+    partnership_rate: f64,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    partnership_runs: u32,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    partnership_wicket: u32,
+}
+
+impl FoW {
+    fn is_current_partnership(&self) -> bool {
+        self.live_current_name.as_deref() == Some("current partnership")
+    }
+
+    fn to_partnership(&self, balls_per_over: u32) -> wicketick::Partnership {
+        wicketick::Partnership {
+            wicket: self.partnership_wicket,
+            runs: self.partnership_runs,
+            overs: wicketick::Overs::from_str_with_default(&self.partnership_overs, balls_per_over),
+            run_rate: self.partnership_rate,
+        }
+    }
+
+    fn to_fall_of_wicket(&self, batter_name: &str, balls_per_over: u32) -> wicketick::FallOfWicket {
+        wicketick::FallOfWicket {
+            wicket: self.partnership_wicket,
+            runs: self.fow_runs,
+            overs: wicketick::Overs::from_str_with_default(&self.fow_overs, balls_per_over),
+            batter: batter_name.to_string(),
+            batter_runs: self.out_player.runs,
+            batter_balls: self.out_player.balls_faced,
+            dismissal: self.out_player.dismissal_string.trim().to_string(),
+            partnership: self.to_partnership(balls_per_over),
+        }
+    }
+}
+
+// OutPlayer is sent as {} when nobody is out
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+struct OutPlayer {
+    player_id: Option<u64>,
+    runs: Option<u32>,
+    balls_faced: Option<u32>,
+    dismissal_string: String,
+}
 
 #[derive(Deserialize, Debug)]
 struct Team {
@@ -159,60 +718,393 @@ struct Team {
     team_id: String,
     team_name: String,
     team_short_name: String,
+    team_abbreviation: String,
+}
+
+impl Team {
+    fn to_wicketick(&self) -> wicketick::Team {
+        wicketick::Team {
+            id: self.team_id.clone(),
+            name: self.team_name.clone(),
+            short_name: self.team_short_name.clone(),
+            abbreviation: self.team_abbreviation.clone(),
+        }
+    }
 }
 
 impl Summary {
     pub fn into(self) -> wicketick::SimpleSummary {
-        let bowler_count = self.live.bowling.len();
-        let batter_count = self.live.batting.len();
+        let balls_per_over = self.matchinfo.bpo;
+        let map_batter = |b: &Batter| {
+            b.clone()
+                .into_wicketick(&self.lookup_player_name(&b.player_id))
+        };
+        let map_bowler = |b: &Bowler| {
+            b.clone()
+                .into_wicketick(&self.lookup_player_name(&b.player_id), balls_per_over)
+        };
 
-        let map_batter = |b: Batter| {
-            let id = b.clone().player_id;
-            Some(b.to_wicketick(&self.lookup_player_name(&id)))
+        // either side can be short, e.g. only one batter in just after a wicket
+        let mut batters = self.live.batting.iter().map(map_batter);
+        let mut bowlers = self.live.bowling.iter().map(map_bowler);
+        let active_players = wicketick::ActivePlayers {
+            batter_one: batters.next(),
+            batter_two: batters.next(),
+            bowler_one: bowlers.next(),
+            bowler_two: bowlers.next(),
         };
-        let map_bowler = |b: Bowler| {
-            let id = b.clone().player_id;
-            Some(b.to_wicketick(&self.lookup_player_name(&id)))
+
+        // prefer the innings array's copy of the live innings, it has the extras
+        let current_innings = match &self.live.innings {
+            Some(live_innings) => self
+                .innings
+                .iter()
+                .find(|i| i.innings_number == live_innings.innings_number)
+                .unwrap_or(live_innings)
+                .clone()
+                .into_wicketick(balls_per_over),
+            None => wicketick::Innings::default(),
         };
 
-        let active_players = match bowler_count + batter_count {
-            4 => wicketick::ActivePlayers {
-                batter_one: map_batter(self.live.batting[0].clone()),
-                batter_two: map_batter(self.live.batting[1].clone()),
-                bowler_one: map_bowler(self.live.bowling[0].clone()),
-                bowler_two: map_bowler(self.live.bowling[1].clone()),
-            },
-            _ => wicketick::ActivePlayers::default(),
+        let status = self.matchinfo.status(&self.live);
+
+        // live only has the last wicket and the current partnership, the WickeTick
+        // holds on to the earlier wickets as they fall
+        let mut fow = self.live.fow.clone();
+        fow.sort_by_key(|f| f.fow_order);
+        let partnership = fow
+            .iter()
+            .find(|f| f.is_current_partnership())
+            .map(|f| f.to_partnership(balls_per_over));
+        let fall_of_wickets = fow
+            .iter()
+            .filter(|f| !f.is_current_partnership())
+            .filter_map(|f| {
+                let player_id = f.out_player.player_id?;
+                Some(f.to_fall_of_wicket(
+                    &self.lookup_player_name(&player_id.to_string()),
+                    balls_per_over,
+                ))
+            })
+            .collect();
+
+        let recent_overs = self
+            .live
+            .recent_overs
+            .iter()
+            .filter(|over| !over.is_empty())
+            .map(|over| wicketick::RecentOver {
+                number: over[0].over_number,
+                balls: over.iter().map(|b| b.to_wicketick()).collect(),
+            })
+            .collect();
+
+        let commentary = self
+            .comms
+            .into_iter()
+            .flat_map(|over| over.ball)
+            .map(|ball| ball.into_wicketick())
+            .collect();
+
+        let match_summary = wicketick::MatchSummary {
+            teams: self.team.iter().map(|t| t.to_wicketick()).collect(),
+            innings: self
+                .innings
+                .into_iter()
+                .map(|i| i.into_wicketick(balls_per_over))
+                .collect(),
+            balls_per_over,
         };
 
-        return wicketick::SimpleSummary {
-            current_innings: wicketick::Innings {
-                runs: self.live.innings.runs as u32,
-                wickets: self.live.innings.wickets as u32,
-                overs: self.live.innings.overs,
-                target: match self.live.innings.target {
+        wicketick::SimpleSummary {
+            current_innings,
+            match_summary,
+            partnership,
+            fall_of_wickets,
+            recent_overs,
+            status,
+            status_text: decode_html_entities(&self.live.status),
+            commentary,
+            active_players,
+            debug_string: "".to_string(),
+        }
+    }
+}
+
+// Listing is the part of a match json we need to list matches to choose from
+#[derive(Deserialize, Debug)]
+struct Listing {
+    #[serde(rename = "match")]
+    matchinfo: MatchInfo,
+    other_scores: OtherScores,
+}
+
+impl Listing {
+    fn into_listings(self) -> Vec<MatchListing> {
+        let mut listings: Vec<MatchListing> = [
+            ("international", self.other_scores.international),
+            ("domestic", self.other_scores.domestic),
+            ("other", self.other_scores.others),
+        ]
+        .into_iter()
+        .flat_map(|(category, scores)| {
+            scores
+                .into_iter()
+                .map(move |score| score.into_listing(category))
+        })
+        .collect();
+        // stable, so each state keeps cricinfo's ordering
+        listings.sort_by_key(|l| l.state);
+        listings
+    }
+}
+
+// parse_gmt_datetime turns cricinfo's "2024-07-12 17:30:00" into seconds since the epoch
+fn parse_gmt_datetime(datetime: &str) -> Option<i64> {
+    let (date, time) = datetime.trim().split_once(' ')?;
+    let mut date = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+    let (hour, minute, second) = (
+        time.next()??,
+        time.next()??,
+        time.next().unwrap_or(Some(0))?,
+    );
+
+    // days since 1970-01-01 in the proleptic gregorian calendar
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+#[derive(Deserialize, Debug)]
+struct MatchInfo {
+    team1_abbreviation: String,
+    team2_abbreviation: String,
+    start_datetime_local: String,
+    start_datetime_gmt: String,
+    // cricinfo's idea of now, handy for working out how long until the start
+    #[serde(default)]
+    present_datetime_gmt: String,
+    // e.g. dormant, current, complete
+    match_status: String,
+    result_name: String,
+    #[serde(deserialize_with = "deserialize_stringy_int")]
+    winner_team_id: u32,
+    // balls per over, 5 in the hundred
+    #[serde(
+        default = "wicketick::default_balls_per_over",
+        deserialize_with = "deserialize_stringy_int"
+    )]
+    bpo: u32,
+}
+
+impl MatchInfo {
+    fn status(&self, live: &LiveState) -> wicketick::MatchStatus {
+        use wicketick::MatchStatus;
+
+        let result = decode_html_entities(&self.result_name);
+        if self.match_status == "complete" || !result.is_empty() {
+            return MatchStatus::Result {
+                margin: result
+                    .split_once(" won by ")
+                    .map(|(_, margin)| margin.to_string()),
+                winner_team_id: match self.winner_team_id {
                     0 => None,
-                    n => Some(n),
+                    id => Some(id.to_string()),
                 },
+                description: result,
+            };
+        }
+        if self.match_status != "current" {
+            let starts_in = match (
+                parse_gmt_datetime(&self.start_datetime_gmt),
+                parse_gmt_datetime(&self.present_datetime_gmt),
+            ) {
+                (Some(start), Some(now)) => {
+                    Some(Duration::from_secs(start.saturating_sub(now).max(0) as u64))
+                }
+                _ => None,
+            };
+            return MatchStatus::Scheduled {
+                start: format!("{} GMT", self.start_datetime_gmt),
+                starts_in,
+            };
+        }
+
+        let break_name = live.break_name.trim().to_lowercase();
+        let live_status = live.status.to_lowercase();
+        let is_weather = |s: &str| {
+            ["rain", "wet", "bad light", "delay"]
+                .iter()
+                .any(|w| s.contains(w))
+        };
+        if break_name.contains("stumps") {
+            MatchStatus::Stumps
+        } else if break_name.contains("innings") {
+            MatchStatus::InningsBreak
+        } else if is_weather(&break_name) || is_weather(&live_status) {
+            MatchStatus::RainDelay
+        } else if !break_name.is_empty() {
+            MatchStatus::Break(live.break_name.trim().to_string())
+        } else {
+            MatchStatus::Live
+        }
+    }
+
+    fn into_listing(self, match_id: &str) -> MatchListing {
+        MatchListing {
+            match_id: match_id.to_string(),
+            state: match self.match_status.as_str() {
+                "current" => wicketick::ListingState::Live,
+                "complete" => wicketick::ListingState::Recent,
+                _ => wicketick::ListingState::Upcoming,
             },
-            active_players,
-            debug_string: "".to_string(),
+            category: "local".to_string(),
+            team_one: wicketick::ListedTeam {
+                name: self.team1_abbreviation,
+                score: "".to_string(),
+            },
+            team_two: wicketick::ListedTeam {
+                name: self.team2_abbreviation,
+                score: "".to_string(),
+            },
+            start_time: self.start_datetime_local,
+            result: match self.result_name.as_str() {
+                "" => None,
+                _ => Some(self.result_name),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct OtherScores {
+    international: Vec<OtherScore>,
+    domestic: Vec<OtherScore>,
+    others: Vec<OtherScore>,
+}
+
+#[derive(Deserialize, Debug)]
+struct OtherScore {
+    object_id: u64,
+    #[serde(default)]
+    result: Option<String>,
+    start_time: String,
+    team1_desc: String,
+    team1_name: String,
+    team2_desc: String,
+    team2_name: String,
+}
+
+impl OtherScore {
+    fn into_listing(self, category: &str) -> MatchListing {
+        let team_one_score = decode_html_entities(&self.team1_desc).trim().to_string();
+        let team_two_score = decode_html_entities(&self.team2_desc).trim().to_string();
+        let state = match (&self.result, team_one_score.is_empty()) {
+            (Some(_), _) => wicketick::ListingState::Recent,
+            (None, true) => wicketick::ListingState::Upcoming,
+            (None, false) => wicketick::ListingState::Live,
         };
+        MatchListing {
+            match_id: self.object_id.to_string(),
+            state,
+            category: category.to_string(),
+            team_one: wicketick::ListedTeam {
+                name: self.team1_name,
+                score: team_one_score,
+            },
+            team_two: wicketick::ListedTeam {
+                name: self.team2_name,
+                score: team_two_score,
+            },
+            start_time: decode_html_entities(&self.start_time),
+            result: self.result.map(|r| decode_html_entities(&r)),
+        }
+    }
+}
+
+// cricinfo sprinkles html entities through its free text fields
+fn decode_html_entities(s: &str) -> String {
+    s.replace("&nbsp;", " ")
+        .replace("&bull;", "\u{2022}")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+// deserialize_stringy_float accepts a float, an integer or a string of either
+fn deserialize_stringy_float<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StringyFloatVisitor;
+
+    impl<'de> serde::de::Visitor<'de> for StringyFloatVisitor {
+        type Value = f64;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a number or a string of a number")
+        }
+
+        fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(value)
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(value as f64)
+        }
+
+        fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(value as f64)
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            value
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| serde::de::Error::custom(format!("invalid number: {:?}", value)))
+        }
     }
+
+    deserializer.deserialize_any(StringyFloatVisitor)
 }
 
 // reference https://serde.rs/stream-array.html
-// could make this genric where T (instead of u32) as long as we have a trait for parsing from string?
-fn deserialize_stringy_int<'de, D>(deserializer: D) -> Result<u32, D::Error>
+// cricinfo is inconsistent about whether numbers are sent as numbers or strings,
+// so this accepts either for any integer type.
+fn deserialize_stringy_int<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
+    T: FromStr + TryFrom<i64> + TryFrom<u64>,
 {
-    struct StringyIntVisitor(PhantomData<fn() -> u32>);
+    struct StringyIntVisitor<T>(PhantomData<fn() -> T>);
 
-    impl<'de> serde::de::Visitor<'de> for StringyIntVisitor {
-        /// Return type of this visitor. This visitor computes the max of a
-        /// sequence of values of type T, so the type of the maximum is T.
-        type Value = u32;
+    impl<'de, T> serde::de::Visitor<'de> for StringyIntVisitor<T>
+    where
+        T: FromStr + TryFrom<i64> + TryFrom<u64>,
+    {
+        type Value = T;
 
         fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
             formatter.write_str("an integer or a string of an integer")
@@ -222,36 +1114,289 @@ where
         where
             E: serde::de::Error,
         {
-            let x_safe: u32 = value
-                .try_into()
-                .map_err(|e| serde::de::Error::custom(format!("{:?}", e)))?;
-            Ok(x_safe)
+            T::try_from(value)
+                .map_err(|_| serde::de::Error::custom(format!("integer out of range: {}", value)))
         }
 
         fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            let x_safe: u32 = value
-                .try_into()
-                .map_err(|e| serde::de::Error::custom(format!("{:?}", e)))?;
-            Ok(x_safe)
+            T::try_from(value)
+                .map_err(|_| serde::de::Error::custom(format!("integer out of range: {}", value)))
         }
 
         fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
         where
             E: serde::de::Error,
         {
-            let val = value
-                .parse::<u32>()
-                .map_err(|e| serde::de::Error::custom(format!("invalid integer: {:?}", e)))?;
-            Ok(val)
+            value
+                .trim()
+                .parse::<T>()
+                .map_err(|_| serde::de::Error::custom(format!("invalid integer: {:?}", value)))
         }
     }
 
     // Create the visitor and ask the deserializer to drive it. The
-    // deserializer will call visitor.visit_seq() if a seq is present in
-    // the input data.
+    // deserializer will call the visit method matching what's in the input data.
     let visitor = StringyIntVisitor(PhantomData);
     deserializer.deserialize_any(visitor)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example() -> serde_json::Value {
+        serde_json::from_str(include_str!("../example_matches/example_match.json")).unwrap()
+    }
+
+    fn summary(json: serde_json::Value) -> wicketick::SimpleSummary {
+        serde_json::from_value::<Summary>(json).unwrap().into()
+    }
+
+    #[test]
+    fn every_innings_in_batting_order() {
+        let summary = summary(example());
+        let innings = &summary.match_summary.innings;
+        assert_eq!(innings.len(), 2);
+        assert_eq!(
+            (innings[0].number, innings[0].runs, innings[0].wickets),
+            (1, 187, 6)
+        );
+        assert_eq!(innings[0].target, None);
+        assert_eq!(innings[0].extras.total(), 9);
+        assert_eq!(innings[1].batting_team_id, "1479");
+        assert_eq!(innings[1].overs.display(), "4.2");
+        assert_eq!(innings[1].target, Some(188));
+        assert_eq!(summary.current_innings, innings[1]);
+        assert_eq!(
+            summary.match_summary.display(),
+            "WORCS 187/6 (17) \u{2014} BEARS 29/3 (4.2) need 159"
+        );
+    }
+
+    #[test]
+    fn last_wicket_and_current_partnership() {
+        let summary = summary(example());
+        let partnership = summary.partnership.unwrap();
+        assert_eq!(partnership.wicket, 4);
+        assert_eq!(partnership.display(), "P'ship 11 (9b) @7.33");
+
+        assert_eq!(summary.fall_of_wickets.len(), 1);
+        let fow = &summary.fall_of_wickets[0];
+        assert_eq!(fow.display(), "3-18 (Sam Hain 14, 2.5 ov)");
+        assert_eq!(fow.dismissal, "b Waite");
+        assert_eq!(fow.batter_balls, Some(8));
+        assert_eq!(fow.partnership.runs, 15);
+    }
+
+    #[test]
+    fn ball_tokens_and_extras() {
+        use wicketick::BallOutcome::*;
+
+        let cases = [
+            ("\u{2022}", "", Dot),
+            ("0", "", Dot),
+            ("W", "", Wicket),
+            ("4", "", Runs(4)),
+            ("1", "w", Wide(1)),
+            // a wide with no token still costs a run
+            ("", "wd", Wide(1)),
+            ("5", "nb", NoBall(5)),
+            ("2", "lb", LegBye(2)),
+            ("4", "b", Bye(4)),
+            ("?", "", Other("?".to_string())),
+            ("2", "pen", Other("2pen".to_string())),
+        ];
+        for (token, extras, outcome) in cases {
+            assert_eq!(
+                parse_ball(token, extras),
+                outcome,
+                "{:?} {:?}",
+                token,
+                extras
+            );
+        }
+    }
+
+    #[test]
+    fn recent_overs_from_the_example() {
+        use wicketick::BallOutcome::*;
+
+        let overs = summary(example()).recent_overs;
+        assert_eq!(overs[0].number, 2);
+        assert_eq!(
+            overs[0].balls,
+            vec![Dot, Wicket, Runs(2), Runs(1), Runs(1), Runs(1)]
+        );
+        assert_eq!(overs[1].number, 3);
+        assert_eq!(overs[1].balls[0], Runs(4));
+    }
+
+    // status_with is the example's status with the live break and status text changed
+    fn status_with(break_name: &str, status: &str) -> wicketick::MatchStatus {
+        let mut json = example();
+        json["live"]["break"] = break_name.into();
+        json["live"]["status"] = status.into();
+        summary(json).status
+    }
+
+    #[test]
+    fn status_from_breaks_and_weather() {
+        use wicketick::MatchStatus;
+
+        let summary = summary(example());
+        assert_eq!(summary.status, MatchStatus::Live);
+        assert_eq!(
+            summary.status_text,
+            "Birmingham Bears require another 159 runs with 7 wickets and 12.4 overs remaining"
+        );
+
+        assert_eq!(status_with("Stumps - Day 1", ""), MatchStatus::Stumps);
+        assert_eq!(status_with("Innings break", ""), MatchStatus::InningsBreak);
+        assert_eq!(
+            status_with(" Tea ", ""),
+            MatchStatus::Break("Tea".to_string())
+        );
+        assert_eq!(status_with("Rain stops play", ""), MatchStatus::RainDelay);
+        assert_eq!(
+            status_with("", "Play delayed by bad light"),
+            MatchStatus::RainDelay
+        );
+        assert_eq!(status_with("", "Wet outfield"), MatchStatus::RainDelay);
+    }
+
+    #[test]
+    fn status_before_and_after_the_match() {
+        use wicketick::MatchStatus;
+
+        let mut json = example();
+        json["match"]["match_status"] = "dormant".into();
+        json["match"]["present_datetime_gmt"] = "2024-07-12 16:30:00".into();
+        assert_eq!(
+            summary(json).status,
+            MatchStatus::Scheduled {
+                start: "2024-07-12 17:30:00 GMT".to_string(),
+                starts_in: Some(Duration::from_secs(3600)),
+            }
+        );
+
+        let mut json = example();
+        json["match"]["match_status"] = "complete".into();
+        json["match"]["result_name"] = "Worcestershire won by 18 runs".into();
+        json["match"]["winner_team_id"] = "1509".into();
+        assert_eq!(
+            summary(json).status,
+            MatchStatus::Result {
+                description: "Worcestershire won by 18 runs".to_string(),
+                winner_team_id: Some("1509".to_string()),
+                margin: Some("18 runs".to_string()),
+            }
+        );
+
+        let mut json = example();
+        json["match"]["match_status"] = "complete".into();
+        json["match"]["result_name"] = "Match drawn".into();
+        assert_eq!(
+            summary(json).status,
+            MatchStatus::Result {
+                description: "Match drawn".to_string(),
+                winner_team_id: None,
+                margin: None,
+            }
+        );
+    }
+
+    #[test]
+    fn listings_live_then_upcoming_then_recent() {
+        use wicketick::ListingState;
+
+        let listing: Listing = serde_json::from_value(example()).unwrap();
+        let listings = listing.into_listings();
+        assert_eq!(listings.len(), 17);
+        let ids_in = |state| {
+            listings
+                .iter()
+                .filter(|l| l.state == state)
+                .map(|l| l.match_id.as_str())
+                .collect::<Vec<&str>>()
+        };
+        assert_eq!(
+            ids_in(ListingState::Live),
+            ["1410472", "1410465", "1410466", "1410470", "1410471", "1410467"]
+        );
+        assert_eq!(
+            ids_in(ListingState::Upcoming),
+            ["1442135", "1428473", "1439770", "1439771"]
+        );
+        assert!(listings[..10]
+            .iter()
+            .all(|l| l.state != ListingState::Recent));
+
+        let test = &listings[10];
+        assert_eq!(test.match_id, "1385691");
+        assert_eq!(test.category, "international");
+        assert_eq!(test.team_one.display(), "WI 121 & 136 (47 ov)");
+        assert_eq!(
+            test.result.as_deref(),
+            Some("England won by an innings and 114 runs")
+        );
+        assert_eq!(listings[0].start_time, "18:30 local | 17:30 GMT");
+    }
+
+    #[test]
+    fn html_entities_decode_once() {
+        assert_eq!(
+            decode_html_entities(
+                "4.2/17&nbsp;ov &bull; &quot;Worcs&quot; &#39;n&#39; &lt;Bears&gt;"
+            ),
+            "4.2/17 ov \u{2022} \"Worcs\" 'n' <Bears>"
+        );
+        // an escaped entity stays escaped
+        assert_eq!(decode_html_entities("&amp;lt;"), "&lt;");
+    }
+
+    #[test]
+    fn gmt_datetimes_across_leap_days() {
+        assert_eq!(parse_gmt_datetime("1970-01-01 00:00:00"), Some(0));
+        assert_eq!(parse_gmt_datetime("2024-02-29 12:00:00"), Some(1709208000));
+        assert_eq!(parse_gmt_datetime("2000-02-29 00:00:00"), Some(951782400));
+        // 1900 wasn't a leap year
+        assert_eq!(parse_gmt_datetime("1900-03-01 00:00:00"), Some(-2203891200));
+        let day = |date: &str| parse_gmt_datetime(&format!("{} 00:00:00", date)).unwrap();
+        assert_eq!(day("2024-03-01") - day("2024-02-28"), 2 * 86400);
+        assert_eq!(day("2023-03-01") - day("2023-02-28"), 86400);
+
+        // seconds are optional, the rest isn't
+        assert_eq!(
+            parse_gmt_datetime("2024-07-12 17:30"),
+            parse_gmt_datetime("2024-07-12 17:30:00")
+        );
+        assert_eq!(parse_gmt_datetime("2024-07-12"), None);
+        assert_eq!(parse_gmt_datetime("tomorrow 17:30:00"), None);
+    }
+
+    #[test]
+    fn active_players_split_by_role() {
+        let players = summary(example()).active_players;
+        assert_eq!(players.batters().len(), 2);
+        assert_eq!(players.bowlers().len(), 2);
+
+        // just after a wicket, with the new batter not in yet
+        let mut json = example();
+        json["live"]["batting"].as_array_mut().unwrap().pop();
+        let players = summary(json).active_players;
+        assert_eq!(players.batters().len(), 1);
+        assert_eq!(players.bowlers().len(), 2);
+
+        // more listed than fit on the ticker
+        let mut json = example();
+        let batter = json["live"]["batting"][0].clone();
+        json["live"]["batting"].as_array_mut().unwrap().push(batter);
+        json["live"]["bowling"].as_array_mut().unwrap().pop();
+        let players = summary(json).active_players;
+        assert_eq!(players.batters().len(), 2);
+        assert_eq!(players.bowlers().len(), 1);
+    }
+}
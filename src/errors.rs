@@ -20,9 +20,27 @@ pub enum Error {
         source: reqwest::Error,
     },
 
-    #[error("parse error")]
+    #[error("config error {source}")]
+    TomlError {
+        #[from]
+        source: toml::de::Error,
+    },
+
+    #[error("parse error {0}")]
     ParseError(String),
 
-    #[error("TODO error")]
+    #[error("no match id")]
+    NoMatchId,
+
+    #[error("not modified, but nothing cached")]
+    NotModifiedUncached,
+
+    #[error("no match to list the others from, set discovery_matches in the config or pass --discovery-match")]
+    NoDiscoveryMatch,
+
+    #[error("no recorded responses in {0}")]
+    EmptySession(String),
+
+    #[error("{0}")]
     Todo(String),
 }
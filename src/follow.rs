@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+
+use crate::errors::Error;
+use crate::wicketick::{MatchListing, MatchSource, MatchStatus, SimpleSummary, Team};
+
+// FollowTeam tracks whichever match a team is playing in, finding it through another
+// source's listing. It waits while the team isn't playing, and moves on to their next
+// match once the one it's following is over.
+pub struct FollowTeam {
+    discovery: Arc<dyn MatchSource>,
+    team: String,
+    state: Mutex<FollowState>,
+}
+
+#[derive(Default)]
+struct FollowState {
+    // the match being followed and the source tracking it
+    current: Option<(String, Arc<dyn MatchSource>)>,
+    // the teams in each match we've had to fetch to see who's playing
+    teams: HashMap<String, Vec<Team>>,
+    // the team being followed, once it's turned up in a match we've fetched
+    known: Option<Team>,
+    // matches we've already seen through to the result
+    finished: Vec<String>,
+    // the last summary of a followed match, to keep showing until the next one
+    last: Option<SimpleSummary>,
+}
+
+impl FollowTeam {
+    pub fn new(discovery: Arc<dyn MatchSource>, team: String) -> Self {
+        Self {
+            discovery,
+            team,
+            state: Mutex::new(FollowState::default()),
+        }
+    }
+
+    // find_match is the team's live match if they have one, otherwise their next one,
+    // otherwise the one they've just finished, along with a source tracking it
+    async fn find_match(&self) -> Result<Option<(String, Arc<dyn MatchSource>)>, Error> {
+        let mut listings = self.discovery.list_matches().await?;
+        let (finished, mut teams, known) = {
+            let state = self.state.lock().await;
+            (
+                state.finished.clone(),
+                state.teams.clone(),
+                state.known.clone(),
+            )
+        };
+        listings.retain(|l| !finished.contains(&l.match_id));
+        listings.sort_by_key(|l| l.state);
+
+        // once we know the team, its id and listed abbreviation pick out its matches
+        // without guessing
+        if let Some(known) = &known {
+            let listing = listings.into_iter().find(|l| {
+                l.involves(&self.team)
+                    || [&l.team_one, &l.team_two]
+                        .iter()
+                        .any(|t| known.goes_by(&t.name))
+                    || teams
+                        .get(&l.match_id)
+                        .is_some_and(|t| t.iter().any(|t| t.id == known.id))
+            });
+            return Ok(listing.map(|l| (l.match_id.clone(), self.discovery.select_match(&l))));
+        }
+
+        // listings mostly only have abbreviations, so fetch the matches that might be
+        // the team's to find out for sure, all at once and without holding the state
+        let mut fetches = JoinSet::new();
+        for listing in &listings {
+            if listing.involves(&self.team) || teams.contains_key(&listing.match_id) {
+                continue;
+            }
+            if listing.might_involve(&self.team) {
+                let source = self.discovery.select_match(listing);
+                let match_id = listing.match_id.clone();
+                fetches.spawn(async move {
+                    let summary = source.fetch_summary().await;
+                    (match_id, source, summary)
+                });
+            }
+        }
+        let mut sources = HashMap::new();
+        while let Some(fetched) = fetches.join_next().await {
+            // one that failed just gets tried again next time round
+            if let Ok((match_id, source, Ok(summary))) = fetched {
+                teams.insert(match_id.clone(), summary.match_summary.teams);
+                sources.insert(match_id, source);
+            }
+        }
+        let found = teams
+            .values()
+            .flatten()
+            .find(|t| t.goes_by(&self.team))
+            .cloned();
+        {
+            let mut state = self.state.lock().await;
+            state.teams.extend(teams.clone());
+            state.known = found.clone();
+        }
+
+        let plays_in = |listing: &MatchListing| {
+            listing.involves(&self.team)
+                || teams.get(&listing.match_id).is_some_and(|t| {
+                    t.iter()
+                        .any(|t| found.as_ref().is_some_and(|f| f.id == t.id))
+                })
+        };
+        let Some(listing) = listings.into_iter().find(plays_in) else {
+            return Ok(None);
+        };
+        // the source we checked the match with already has it, so keep that one
+        let source = match sources.remove(&listing.match_id) {
+            Some(source) => source,
+            None => self.discovery.select_match(&listing),
+        };
+        Ok(Some((listing.match_id, source)))
+    }
+
+    fn waiting(&self) -> SimpleSummary {
+        SimpleSummary {
+            status: MatchStatus::Scheduled {
+                start: format!("when {} next play", self.team),
+                starts_in: None,
+            },
+            ..SimpleSummary::default()
+        }
+    }
+}
+
+#[async_trait]
+impl MatchSource for FollowTeam {
+    async fn fetch_summary(&self) -> Result<SimpleSummary, Error> {
+        let mut current = self.state.lock().await.current.clone();
+        if current.is_none() {
+            current = self.find_match().await?;
+            self.state.lock().await.current = current.clone();
+        }
+
+        let Some((match_id, source)) = current else {
+            let state = self.state.lock().await;
+            return Ok(state.last.clone().unwrap_or_else(|| self.waiting()));
+        };
+        let summary = source.fetch_summary().await?;
+        let mut state = self.state.lock().await;
+        if state.known.is_none() {
+            let teams = &summary.match_summary.teams;
+            state.known = teams.iter().find(|t| t.goes_by(&self.team)).cloned();
+        }
+        if summary.status.is_finished() {
+            // show the result for now, and look for their next match next time
+            state.finished.push(match_id);
+            state.current = None;
+        }
+        state.last = Some(summary.clone());
+        Ok(summary)
+    }
+
+    async fn list_matches(&self) -> Result<Vec<MatchListing>, Error> {
+        self.discovery.list_matches().await
+    }
+
+    fn select_match(&self, listing: &MatchListing) -> Arc<dyn MatchSource> {
+        self.discovery.select_match(listing)
+    }
+
+    fn should_poll(&self) -> bool {
+        true
+    }
+
+    fn follows_on(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> String {
+        format!("{} following {}", self.discovery.name(), self.team)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::wicketick::{ListedTeam, ListingState};
+
+    // Listed offers two live matches, NOTTS v LANCS and NHAMPS v DERBY, and counts
+    // how many times each gets fetched
+    struct Listed {
+        match_id: Option<String>,
+        fetches: Arc<AtomicUsize>,
+    }
+
+    fn team(id: &str, name: &str, abbreviation: &str) -> Team {
+        Team {
+            id: id.to_string(),
+            name: name.to_string(),
+            short_name: name.to_string(),
+            abbreviation: abbreviation.to_string(),
+        }
+    }
+
+    fn listing(match_id: &str, one: &str, two: &str) -> MatchListing {
+        let listed = |name: &str| ListedTeam {
+            name: name.to_string(),
+            score: "".to_string(),
+        };
+        MatchListing {
+            match_id: match_id.to_string(),
+            state: ListingState::Live,
+            category: "domestic".to_string(),
+            team_one: listed(one),
+            team_two: listed(two),
+            start_time: "".to_string(),
+            result: None,
+        }
+    }
+
+    #[async_trait]
+    impl MatchSource for Listed {
+        async fn fetch_summary(&self) -> Result<SimpleSummary, Error> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let mut summary = SimpleSummary::default();
+            summary.match_summary.teams = match self.match_id.as_deref() {
+                Some("1") => vec![
+                    team("10", "Nottinghamshire", "NOTTS"),
+                    team("11", "Lancashire", "LANCS"),
+                ],
+                _ => vec![
+                    team("12", "Northamptonshire", "NHAMPS"),
+                    team("13", "Derbyshire", "DERBY"),
+                ],
+            };
+            Ok(summary)
+        }
+
+        async fn list_matches(&self) -> Result<Vec<MatchListing>, Error> {
+            Ok(vec![
+                listing("1", "NOTTS", "LANCS"),
+                listing("2", "NHAMPS", "DERBY"),
+            ])
+        }
+
+        fn select_match(&self, listing: &MatchListing) -> Arc<dyn MatchSource> {
+            Arc::new(Listed {
+                match_id: Some(listing.match_id.clone()),
+                fetches: self.fetches.clone(),
+            })
+        }
+
+        fn should_poll(&self) -> bool {
+            true
+        }
+
+        fn name(&self) -> String {
+            "listed".to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn guesses_once_then_goes_by_the_known_team() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let discovery = Arc::new(Listed {
+            match_id: None,
+            fetches: fetches.clone(),
+        });
+        let follow = FollowTeam::new(discovery, "Northamptonshire".to_string());
+
+        // NOTTS looks like it could be short for Northamptonshire, so both get checked
+        let (match_id, _) = follow.find_match().await.unwrap().unwrap();
+        assert_eq!(match_id, "2");
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        let (match_id, _) = follow.find_match().await.unwrap().unwrap();
+        assert_eq!(match_id, "2");
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,93 @@
+use ratatui::crossterm::event::KeyCode;
+
+use crate::config::KeyBindings;
+
+// Action is something the user can ask of a phase, whichever key it's bound to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    // back to the previous phase, or out of whatever the phase has open
+    Back,
+    Help,
+    Refresh,
+    // switches between the minimal and relaxed tickers
+    Layout,
+    Commentary,
+    Details,
+    Up,
+    Down,
+    Select,
+    Add,
+    Remove,
+    // one of a numbered list of options, from the number keys
+    Choose(u32),
+}
+
+// ActionMap is which keys do what, built from the key bindings in the config
+#[derive(Clone, Debug)]
+pub struct ActionMap {
+    bindings: Vec<(KeyCode, Action)>,
+}
+
+impl ActionMap {
+    pub fn new(keys: &KeyBindings) -> Self {
+        Self {
+            bindings: vec![
+                (KeyCode::Char(keys.quit), Action::Quit),
+                (KeyCode::Esc, Action::Back),
+                (KeyCode::Backspace, Action::Back),
+                (KeyCode::Char(keys.help), Action::Help),
+                (KeyCode::Char(keys.refresh), Action::Refresh),
+                (KeyCode::Char(keys.layout), Action::Layout),
+                (KeyCode::Char(keys.commentary), Action::Commentary),
+                (KeyCode::Char(keys.details), Action::Details),
+                (KeyCode::Up, Action::Up),
+                (KeyCode::Char(keys.up), Action::Up),
+                (KeyCode::Down, Action::Down),
+                (KeyCode::Char(keys.down), Action::Down),
+                (KeyCode::Enter, Action::Select),
+                (KeyCode::Char(keys.add), Action::Add),
+                (KeyCode::Char(keys.remove), Action::Remove),
+                (KeyCode::Delete, Action::Remove),
+            ],
+        }
+    }
+
+    // action is what the key does, the config makes sure no key is bound twice
+    pub fn action(&self, key: KeyCode) -> Option<Action> {
+        if let Some((_, action)) = self.bindings.iter().find(|(k, _)| *k == key) {
+            return Some(*action);
+        }
+        match key {
+            KeyCode::Char(c) => c.to_digit(10).map(Action::Choose),
+            _ => None,
+        }
+    }
+
+    // keys lists the keys bound to the action, e.g. "k, up"
+    pub fn keys(&self, action: Action) -> String {
+        if let Action::Choose(n) = action {
+            return n.to_string();
+        }
+        self.bindings
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(key, _)| key_name(*key))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+fn key_name(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Delete => "delete".to_string(),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
@@ -6,28 +6,54 @@ use ratatui::{
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
         ExecutableCommand,
     },
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::Stylize,
-    widgets::Paragraph,
-    Terminal,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, Wrap,
+    },
+    Frame, Terminal,
 };
+use wicketick::events;
 use wicketick::{
-    SimpleSummary, Source, WickeTick, DEFAULT_POLL_INTERVAL, DEFAULT_POLL_INTERVAL_SECS,
+    Backoff, Commentary, MatchListing, MatchSource, SimpleSummary, Source, WickeTick,
+    DEFAULT_BACKOFF_MAX_SECS, DEFAULT_MAX_RETRIES, DEFAULT_POLL_INTERVAL,
+    DEFAULT_POLL_INTERVAL_SECS,
 };
 
 use std::{
-    io::{stdout, Stdout},
+    io::{stdout, Stdout, Write},
+    path::PathBuf,
+    sync::Arc,
     time::Duration,
 };
 use tokio::{
-    sync::mpsc::{self, Receiver, Sender},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        oneshot,
+    },
     task::JoinHandle,
+    time::Instant,
 };
 
 pub mod errors;
 use errors::Error;
 
+pub mod cache;
+pub mod config;
+use config::{Config, SourceConfig, TickerLayout};
 pub mod cricinfo;
+pub mod follow;
+pub mod input;
+use input::{Action, ActionMap};
+pub mod notify;
+use notify::{Filter, Hook, Notifier};
+pub mod replay;
+use replay::{Recorder, ReplaySpeed};
+pub mod statusbar;
+use statusbar::{StatusBar, StatusBarFormat};
+pub mod theme;
+use theme::Theme;
 pub mod wicketick;
 
 // todo this needs to be updated to account for different sources
@@ -39,11 +65,104 @@ struct Args {
     source: Option<CliSources>,
 
     // polling interval in seconds
-    #[arg(short, long, default_value_t = DEFAULT_POLL_INTERVAL_SECS)]
-    time_interval: u64,
+    #[arg(short, long)]
+    time_interval: Option<u64>,
+
+    // longest to wait between retries of a failing source, in seconds
+    #[arg(long, default_value_t = DEFAULT_BACKOFF_MAX_SECS)]
+    backoff_max: u64,
+
+    // retries of a failing source before giving up, 0 to never give up
+    #[arg(long, default_value_t = DEFAULT_MAX_RETRIES)]
+    max_retries: u32,
+
+    // always poll every time_interval, rather than slowing down during breaks
+    #[arg(long)]
+    fixed_interval: bool,
+
+    // fetch the summary once, print it and exit without touching the terminal
+    #[arg(long)]
+    once: bool,
+
+    // write a line per poll for a status bar or another program instead of drawing the ticker
+    #[arg(long, value_enum)]
+    output: Option<StatusBarFormat>,
+
+    // follow several matches at once, starting with the source's match if it has one
+    #[arg(long)]
+    dashboard: bool,
+
+    // save every cricinfo response into this directory, one directory per match, to
+    // replay later
+    #[arg(long)]
+    record: Option<String>,
+
+    // send desktop notifications for match events
+    #[arg(long)]
+    notify: bool,
+
+    // shell command to run for match events, gets the event in WICKETICK_* env vars
+    // and as json on stdin
+    #[arg(long)]
+    hook: Vec<String>,
+
+    // only notify about these kinds of event, e.g. wicket, boundary, milestone, result.
+    // Applies to --notify and --hook, hooks in the config file have their own.
+    #[arg(long)]
+    notify_on: Vec<String>,
+
+    // only notify about events involving these teams, like --notify-on
+    #[arg(long)]
+    notify_team: Vec<String>,
+
+    // cricinfo match to list the others from, tried before the favourite matches and
+    // the last match followed
+    #[arg(long)]
+    discovery_match: Vec<String>,
+
+    // config file to use instead of the one in the config dir
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    // which ticker the live stream starts with
+    #[arg(long, value_enum)]
+    ticker: Option<TickerLayout>,
+
+    // colour theme, one of classic, dark, light or mono, or a background colour for
+    // the classic theme like blue or #1e1e2e
+    #[arg(long)]
+    theme: Option<String>,
     // Obviously there could be all sorts of things we do here
 }
 
+impl Args {
+    // apply_config fills in whatever wasn't given on the command line from the config file
+    fn apply_config(&mut self, config: &Config) {
+        self.time_interval = self.time_interval.or(config.poll_interval);
+        self.ticker = self.ticker.or(config.ticker);
+        if self.theme.is_none() {
+            self.theme = config.theme.clone();
+        }
+        if self.source.is_none() {
+            self.source = config.source.clone().map(|source| match source {
+                SourceConfig::Cricinfo { match_id } => CliSources::Cricinfo { match_id },
+                SourceConfig::LocalCricinfo { filename } => CliSources::LocalCricinfo { filename },
+            });
+        }
+        self.discovery_match
+            .extend(config.discovery_matches.iter().cloned());
+        self.discovery_match
+            .extend(config.favourite_matches.iter().cloned());
+        if let Some(CliSources::Follow { team: team @ None }) = &mut self.source {
+            *team = config.favourite_teams.first().cloned();
+        }
+    }
+
+    fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.time_interval.unwrap_or(DEFAULT_POLL_INTERVAL_SECS))
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum CliSources {
     #[command(about = "use cricinfo as the source")]
@@ -57,6 +176,27 @@ enum CliSources {
         #[arg(short, long)]
         filename: String,
     },
+
+    #[command(about = "replay a match's cricinfo responses saved with --record")]
+    Replay {
+        #[arg(short, long)]
+        dir: String,
+
+        // how many times faster than it was recorded to play it back
+        #[arg(short, long, default_value_t = 1.0)]
+        speed: f64,
+
+        // move on one response per poll (or press of r) rather than keeping time
+        #[arg(long)]
+        step: bool,
+    },
+
+    #[command(about = "follow whichever cricinfo match a team is playing in")]
+    Follow {
+        // name or abbreviation of the team, defaults to the first favourite team
+        #[arg(short, long)]
+        team: Option<String>,
+    },
 }
 
 fn terminal_preamble() -> Result<(), Error> {
@@ -71,66 +211,209 @@ fn terminal_teardown() -> Result<(), Error> {
     Ok(())
 }
 
-fn phase_from_args<'a>(args: Args) -> Result<(TickerPhase, Option<JoinHandle<()>>), Error> {
-    match args.source {
-        Some(source) => match source {
-            CliSources::Cricinfo { match_id } => match match_id {
-                Some(_) => {
-                    let source = wicketick::Source::Cricinfo { match_id: match_id };
-                    let w = WickeTick {
-                        source: source.clone(),
-                        summary: None,
-                        last_refresh: None,
-                        poll_interval: Some(Duration::from_secs(args.time_interval)),
-                    };
+fn source_from_args(args: &Args) -> Result<Option<Source>, Error> {
+    match &args.source {
+        Some(CliSources::Cricinfo { match_id }) => Ok(Some(Source::Cricinfo {
+            match_id: match_id.clone(),
+        })),
+        Some(CliSources::LocalCricinfo { filename }) => {
+            if !std::path::Path::new(&filename).exists() {
+                return Err(errors::Error::Todo("file does not exist".to_string()));
+            }
+            Ok(Some(Source::LocalCricinfo {
+                filename: filename.clone(),
+            }))
+        }
+        Some(CliSources::Replay { dir, speed, step }) => {
+            if !std::path::Path::new(&dir).is_dir() {
+                return Err(errors::Error::Todo(
+                    "session dir does not exist".to_string(),
+                ));
+            }
+            let speed = match (step, *speed) {
+                (true, _) => ReplaySpeed::Stepped,
+                (false, speed) if speed > 0.0 => ReplaySpeed::Timed(speed),
+                _ => return Err(errors::Error::Todo("speed must be above 0".to_string())),
+            };
+            Ok(Some(Source::Replay {
+                dir: dir.clone(),
+                speed,
+            }))
+        }
+        Some(CliSources::Follow { team }) => match team {
+            Some(team) => Ok(Some(Source::FollowTeam { team: team.clone() })),
+            None => Err(errors::Error::Todo(
+                "follow needs a --team or a favourite team in the config".to_string(),
+            )),
+        },
+        None => Ok(None),
+    }
+}
+
+// build_match_source builds the source, recording what it fetches if asked to
+fn build_match_source(source: Source, args: &Args) -> Result<Arc<dyn MatchSource>, Error> {
+    let Some(dir) = &args.record else {
+        return Ok(source.into_match_source(&args.discovery_match));
+    };
+    match source {
+        Source::Cricinfo { match_id } => {
+            let recorder = Recorder::new(dir)?;
+            Ok(Arc::new(
+                cricinfo::Cricinfo::new(match_id)
+                    .discovering_from(args.discovery_match.clone())
+                    .recording(recorder),
+            ))
+        }
+        _ => Err(Error::Todo(
+            "--record only works with the cricinfo source".to_string(),
+        )),
+    }
+}
+
+fn wicketick_from_args(source: Source, args: &Args) -> Result<WickeTick, Error> {
+    Ok(Polling::from_args(args).wicketick(build_match_source(source, args)?))
+}
 
-                    // TODO so we can't stop this boy
-                    let (live_stream, stopper) = LiveStream::new(w);
+// Polling is how often to poll and how to cope with failures, which every match
+// gets whichever phase opened it
+#[derive(Clone, Copy)]
+struct Polling {
+    interval: Duration,
+    backoff: Backoff,
+    adaptive: bool,
+}
 
-                    Ok((TickerPhase::LiveStream(live_stream), Some(stopper)))
-                }
-                None => Ok((
-                    TickerPhase::MatchSelect(MatchSelect {
-                        source: wicketick::Source::Cricinfo { match_id: None },
-                    }),
-                    None,
-                )),
+impl Polling {
+    fn from_args(args: &Args) -> Self {
+        Self {
+            interval: args.poll_interval(),
+            backoff: Backoff {
+                max: Duration::from_secs(args.backoff_max),
+                max_retries: match args.max_retries {
+                    0 => None,
+                    n => Some(n),
+                },
+                ..Backoff::default()
             },
-            CliSources::LocalCricinfo { filename } => {
-                match std::path::Path::new(&filename).exists() {
-                    true => {
-                        let source = wicketick::Source::LocalCricinfo { filename };
-                        let w = WickeTick {
-                            source: source.clone(),
-                            summary: None,
-                            last_refresh: None,
-                            poll_interval: Some(Duration::from_secs(args.time_interval)),
-                        };
+            adaptive: !args.fixed_interval,
+        }
+    }
 
-                        let (live_stream, stopper) = LiveStream::new(w);
-                        Ok((TickerPhase::LiveStream(live_stream), Some(stopper)))
-                    }
-                    false => Err(errors::Error::Todo("file does not exist".to_string())),
-                }
-            } // _ => Err(errors::Error::Todo("not sure".to_string())),
-        },
-        None => Ok((TickerPhase::SourceSelect(SourceSelect::new()), None)),
+    fn wicketick(&self, source: Arc<dyn MatchSource>) -> WickeTick {
+        let mut w = WickeTick::new(source, Some(self.interval));
+        w.backoff = self.backoff;
+        w.adaptive = self.adaptive;
+        w
+    }
+}
+
+// notifier_from_args puts the hooks from the command line, which share the command
+// line's filter, together with the config's, which each have their own
+fn notifier_from_args(args: &Args, config: &Config) -> Notifier {
+    let filter = Filter {
+        events: args.notify_on.clone(),
+        teams: args.notify_team.clone(),
+    };
+    let cli_hooks = args
+        .hook
+        .iter()
+        .map(|command| Hook::new(command.clone(), filter.clone()));
+    let config_hooks = config.hooks.iter().map(|hook| {
+        let filter = Filter {
+            events: hook.events.clone(),
+            teams: hook.teams.clone(),
+        };
+        Hook::new(hook.command.clone(), filter)
+    });
+    Notifier {
+        desktop: args
+            .notify
+            .then_some(filter.clone())
+            .or(config.notify.clone()),
+        hooks: cli_hooks.chain(config_hooks).collect(),
+    }
+}
+
+fn phase_from_args(
+    args: Args,
+    settings: &Settings,
+) -> Result<(TickerPhase, Option<JoinHandle<()>>), Error> {
+    let source = match source_from_args(&args)? {
+        Some(source) => source,
+        // the dashboard can always fall back on cricinfo for the favourites
+        None if args.dashboard => Source::Cricinfo { match_id: None },
+        None => {
+            if args.record.is_some() {
+                return Err(Error::Todo("--record needs a source".to_string()));
+            }
+            return Ok((TickerPhase::SourceSelect(SourceSelect::new()), None));
+        }
+    };
+
+    if args.dashboard {
+        let match_id = match &source {
+            Source::Cricinfo { match_id } => match_id.clone(),
+            _ => None,
+        };
+        let has_match = !matches!(source, Source::Cricinfo { match_id: None });
+        let label = source.to_string();
+        let match_source = build_match_source(source, &args)?;
+        let mut dashboard = Dashboard::new(match_source.clone());
+        if has_match {
+            dashboard.add(label, match_source, settings);
+        }
+        dashboard.add_favourites(match_id.as_ref(), settings, |source| {
+            build_match_source(source, &args)
+        })?;
+        return Ok((TickerPhase::Dashboard(Box::new(dashboard)), None));
+    }
+
+    match source {
+        Source::Cricinfo { match_id: None } => Ok((
+            TickerPhase::MatchSelect(MatchSelect::new(build_match_source(source, &args)?)),
+            None,
+        )),
+        _ => {
+            let w = wicketick_from_args(source, &args)?;
+
+            // TODO so we can't stop this boy
+            let (live_stream, stopper) = settings.live_stream(w);
+            Ok((
+                TickerPhase::LiveStream(Box::new(live_stream)),
+                Some(stopper),
+            ))
+        }
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+    args.apply_config(&config);
+
+    if args.once {
+        return print_once(args).await;
+    }
+    if let Some(format) = args.output {
+        return stream_status_bar(format, args, &config).await;
+    }
+
+    // before touching the terminal, so a bad theme gets reported properly
+    let settings = Settings::from_config(&args, &config)?;
 
     terminal_preamble()?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     terminal.clear()?;
 
-    let (phase, stopper) = phase_from_args(args)?;
+    let (phase, stopper) = phase_from_args(args, &settings)?;
 
     let mut state: TickerState = TickerState {
         terminal,
+        settings,
         phase,
+        history: vec![],
+        show_help: false,
         stopper,
     };
 
@@ -174,12 +457,81 @@ async fn main() -> Result<(), Error> {
     Ok(())
 }
 
+// match_source_from_args is for the modes that skip the ticker, so need to be told the match
+fn match_source_from_args(args: &Args, mode: &str) -> Result<Source, Error> {
+    match source_from_args(args)? {
+        Some(Source::Cricinfo { match_id: None }) | None => {
+            Err(Error::Todo(format!("{} needs a match to print", mode)))
+        }
+        Some(source) => Ok(source),
+    }
+}
+
+// print_once fetches a single summary and prints it as plain text, for scripts and prompts
+async fn print_once(args: Args) -> Result<(), Error> {
+    let source = match_source_from_args(&args, "--once")?;
+    let mut w = wicketick_from_args(source, &args)?;
+    let summary = match w.refetch().await {
+        Ok(summary) => summary,
+        Err(e) => {
+            // show what we last knew, marked as such, but still fail
+            w.load_cached();
+            let (Some(summary), Some(cached)) = (&w.summary, w.cached_text()) else {
+                return Err(e);
+            };
+            println!("[{}] {}", cached, summary.display());
+            return Err(e);
+        }
+    };
+
+    println!("{}", summary.display());
+    println!("{}", summary.display_status());
+    for line in [
+        summary.active_players.display_batters(),
+        summary.active_players.display_bowlers(),
+    ] {
+        if !line.trim().is_empty() {
+            println!("{}", line.trim());
+        }
+    }
+    Ok(())
+}
+
+// stream_status_bar prints a line for a status bar every time the poller has an update
+async fn stream_status_bar(
+    format: StatusBarFormat,
+    args: Args,
+    config: &Config,
+) -> Result<(), Error> {
+    let source = match_source_from_args(&args, "--output")?;
+    let (mut live_stream, _stopper) = LiveStream::new(
+        wicketick_from_args(source, &args)?,
+        notifier_from_args(&args, config),
+    );
+
+    let mut bar = StatusBar::new(format);
+    let mut out = stdout();
+    if let Some(header) = bar.header() {
+        writeln!(out, "{}", header)?;
+    }
+    while live_stream.wait_for_update().await {
+        let line = bar.line(
+            live_stream.wicketick.summary.as_ref(),
+            live_stream.last_error.as_deref(),
+        )?;
+        // whatever was reading us has gone away, so there's no one left to write for
+        if writeln!(out, "{}", line).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
 async fn update(state: &mut TickerState) -> Result<(), Error> {
     // calculate what we want to display
 
-    match &mut state.phase {
-        TickerPhase::LiveStream(live_stream) => live_stream.consume_update(),
-        _ => {}
+    if let TickerPhase::LiveStream(live_stream) = &mut state.phase {
+        live_stream.consume_update()
     }
 
     let Some(phase) = state.phase.as_inner_trait() else {
@@ -195,28 +547,132 @@ async fn draw(state: &mut TickerState) -> Result<(), Error> {
     let Some(phase) = state.phase.as_inner_trait() else {
         return Err(Error::Todo("draw failed to get trait".to_string()));
     };
-    phase.draw(&mut state.terminal)
+    let settings = &state.settings;
+    let help = match state.show_help {
+        true => Some(help_bindings(
+            phase.actions(),
+            settings,
+            !state.history.is_empty(),
+        )),
+        false => None,
+    };
+    state.terminal.draw(|frame| {
+        phase.draw(frame, settings);
+        if let Some(help) = &help {
+            draw_help(frame, help, settings);
+        }
+    })?;
+    Ok(())
+}
+
+// help_bindings are the keys for everything the phase does, followed by the ones
+// every phase has
+fn help_bindings(
+    mut actions: Vec<(Action, &'static str)>,
+    settings: &Settings,
+    can_go_back: bool,
+) -> Vec<(String, &'static str)> {
+    if can_go_back && !actions.iter().any(|(a, _)| *a == Action::Back) {
+        actions.push((Action::Back, "back to the previous screen"));
+    }
+    actions.push((Action::Help, "show or hide this help"));
+    actions.push((Action::Quit, "quit"));
+    actions
+        .into_iter()
+        .map(|(action, what)| (settings.actions.keys(action), what))
+        .collect()
+}
+
+// draw_help lays the key bindings over the middle of the phase
+fn draw_help(frame: &mut Frame, bindings: &[(String, &'static str)], settings: &Settings) {
+    let key_width = bindings
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+    let lines: Vec<Line> = bindings
+        .iter()
+        .map(|(keys, what)| {
+            Line::from(vec![
+                Span::raw(format!("{:<width$}  ", keys, width = key_width)).bold(),
+                Span::raw(*what),
+            ])
+        })
+        .collect();
+
+    let area = frame.size();
+    let width = (lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 4).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let help = Paragraph::new(lines)
+        .block(Block::default().title("Keys").borders(Borders::ALL))
+        .style(settings.theme.menu);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(help, popup);
 }
 
 async fn handle_input(state: &mut TickerState) -> Result<bool, Error> {
-    let Some(phase) = state.phase.as_inner_trait() else {
-        return Err(Error::Todo("handle input failed to get trait".to_string()));
+    let Some(key) = input_key_press()? else {
+        return Ok(false);
+    };
+    let Some(action) = state.settings.actions.action(key) else {
+        return Ok(false);
     };
 
-    let new_phase = phase.handle_input()?;
+    // the help sits on top of the phase, so gets first go at the keys
+    if state.show_help {
+        match action {
+            Action::Quit => return Ok(true),
+            Action::Help | Action::Back => state.show_help = false,
+            _ => {}
+        }
+        return Ok(false);
+    }
+    if action == Action::Help {
+        state.show_help = true;
+        return Ok(false);
+    }
 
-    match new_phase.phase {
-        Some(phase) => {
-            if let Some(join_handle) = &state.stopper {
-                join_handle.abort();
-            }
-            state.phase = phase;
-            state.stopper = new_phase.stopper;
+    let Some(phase) = state.phase.as_inner_trait() else {
+        return Err(Error::Todo("handle input failed to get trait".to_string()));
+    };
+    // anything the phase doesn't want does the same as it would anywhere else
+    let Some(response) = phase.handle_action(action, &state.settings) else {
+        match action {
+            Action::Quit => return Ok(true),
+            Action::Back => go_back(state),
+            _ => {}
         }
-        None => {}
+        return Ok(false);
+    };
+
+    if let Some(phase) = response.phase {
+        // keep the old phase around to go back to, pollers and all
+        let previous = std::mem::replace(&mut state.phase, phase);
+        let stopper = std::mem::replace(&mut state.stopper, response.stopper);
+        state.history.push((previous, stopper));
     }
+    Ok(false)
+}
 
-    return Ok(new_phase.should_close);
+// go_back returns to the previous phase, stopping the current one's poller
+fn go_back(state: &mut TickerState) {
+    let Some((phase, stopper)) = state.history.pop() else {
+        return;
+    };
+    if let Some(join_handle) = &state.stopper {
+        join_handle.abort();
+    }
+    state.phase = phase;
+    state.stopper = stopper;
+    if let Some(phase) = state.phase.as_inner_trait() {
+        phase.resume();
+    }
 }
 
 fn input_key_press() -> Result<Option<KeyCode>, Error> {
@@ -232,18 +688,66 @@ fn input_key_press() -> Result<Option<KeyCode>, Error> {
 // If we need to know things for each of these states, we can add it
 struct TickerState {
     terminal: ratatui::terminal::Terminal<CrosstermBackend<Stdout>>,
+    settings: Settings,
     phase: TickerPhase,
+    // phases to go back to, the latest last, with their pollers still going
+    history: Vec<(TickerPhase, Option<JoinHandle<()>>)>,
+    // the key bindings are showing over the phase
+    show_help: bool,
     stopper: Option<JoinHandle<()>>, // TODO i don't like this
                                      // have a tokio channel and use selectors?
                                      // have a tokio runtime into which we can spawn jobs?
 }
 
+// Settings are the user's preferences that every phase needs to know about
+struct Settings {
+    actions: ActionMap,
+    theme: Theme,
+    favourite_teams: Vec<String>,
+    // who to tell about match events, for every live stream whichever phase opened it
+    notifier: Notifier,
+    polling: Polling,
+    // matches for cricinfo to list the others from
+    discovery: Vec<String>,
+    // the ticker every live stream opens with
+    ticker: TickerConfiguration,
+    // cricinfo matches every dashboard starts with
+    favourite_matches: Vec<String>,
+}
+
+impl Settings {
+    fn from_config(args: &Args, config: &Config) -> Result<Self, Error> {
+        Ok(Self {
+            actions: ActionMap::new(&config.keys),
+            theme: Theme::load(args.theme.as_deref(), &config.team_colours)?,
+            favourite_teams: config.favourite_teams.clone(),
+            notifier: notifier_from_args(args, config),
+            polling: Polling::from_args(args),
+            discovery: args.discovery_match.clone(),
+            ticker: args
+                .ticker
+                .map(TickerConfiguration::from)
+                .unwrap_or(TickerConfiguration::MinimalTicker),
+            favourite_matches: config.favourite_matches.clone(),
+        })
+    }
+
+    // live_stream opens the match with the user's notifier and starting ticker, and
+    // starts polling it
+    fn live_stream(&self, wicketick: WickeTick) -> (LiveStream, JoinHandle<()>) {
+        let (mut live_stream, stopper) = LiveStream::new(wicketick, self.notifier.clone());
+        live_stream.configuration = self.ticker;
+        (live_stream, stopper)
+    }
+}
+
 // Used to control what functionality the UI needs to be providing
 enum TickerPhase {
     SourceSelect(SourceSelect),
     MatchSelect(MatchSelect),
     // TODO probably reduce to just one?
-    LiveStream(LiveStream),
+    LiveStream(Box<LiveStream>),
+    Dashboard(Box<Dashboard>),
 }
 
 impl TickerPhase {
@@ -251,7 +755,8 @@ impl TickerPhase {
         match self {
             TickerPhase::SourceSelect(inner) => Some(inner),
             TickerPhase::MatchSelect(inner) => Some(inner),
-            TickerPhase::LiveStream(inner) => Some(inner),
+            TickerPhase::LiveStream(inner) => Some(inner.as_mut()),
+            TickerPhase::Dashboard(inner) => Some(inner.as_mut()),
         }
     }
 }
@@ -270,11 +775,15 @@ impl TickerPhase {
 // to control things that should happen as we enter and leave the phase...
 trait TickerPhaseTemp {
     fn update(&mut self) -> Result<(), Error>;
-    fn draw(
-        &mut self,
-        terminal: &mut ratatui::terminal::Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<(), Error>;
-    fn handle_input(&mut self) -> Result<HandleInputResponse, Error>;
+    fn draw(&mut self, frame: &mut Frame, settings: &Settings);
+    // handle_action does whatever the action means in this phase, or returns None if it
+    // means nothing here, so the action can do what it does everywhere else
+    fn handle_action(&mut self, action: Action, settings: &Settings)
+        -> Option<HandleInputResponse>;
+    // actions are the actions the phase handles, with what they do, for the help
+    fn actions(&self) -> Vec<(Action, &'static str)>;
+    // resume catches the phase up on whatever it missed while it was in the history
+    fn resume(&mut self) {}
 }
 
 // trait Poller<I, O> {
@@ -284,8 +793,8 @@ trait TickerPhaseTemp {
 //     async fn consume_update(updated_val: O);
 // }
 
+#[derive(Default)]
 struct HandleInputResponse {
-    should_close: bool,
     phase: Option<TickerPhase>,
     stopper: Option<JoinHandle<()>>, // TODO really this should be part of the phase... but i think that leads to problems
 }
@@ -303,124 +812,444 @@ impl TickerPhaseTemp for SourceSelect {
         Ok(())
     }
 
-    fn draw(
-        &mut self,
-        terminal: &mut ratatui::terminal::Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<(), Error> {
-        let widget = Paragraph::new("1. CricInfo\n").white().on_green();
-        terminal.draw(|frame| {
-            let area = frame.size();
-            frame.render_widget(widget, area);
-        })?;
-        Ok(())
+    fn draw(&mut self, frame: &mut Frame, settings: &Settings) {
+        let widget =
+            Paragraph::new("1. CricInfo\n2. CricInfo dashboard\n").style(settings.theme.menu);
+        frame.render_widget(widget, frame.size());
     }
 
-    fn handle_input(&mut self) -> Result<HandleInputResponse, Error> {
-        let mut should_close = false;
-        if let Some(key) = input_key_press()? {
-            match key {
-                KeyCode::Char('q') => {
-                    should_close = true;
-                }
-                KeyCode::Char('1') => {
-                    return Ok(HandleInputResponse {
-                        should_close,
-                        phase: Some(TickerPhase::MatchSelect(MatchSelect::new(
-                            Source::Cricinfo { match_id: None },
-                        ))),
-                        stopper: None,
-                    })
-                }
-                _ => {}
+    fn handle_action(
+        &mut self,
+        action: Action,
+        settings: &Settings,
+    ) -> Option<HandleInputResponse> {
+        let source = || Source::Cricinfo { match_id: None }.into_match_source(&settings.discovery);
+        let phase = match action {
+            Action::Choose(1) => TickerPhase::MatchSelect(MatchSelect::new(source())),
+            Action::Choose(2) => {
+                let mut dashboard = Dashboard::new(source());
+                // plain cricinfo sources always build, it's only recording that can fail
+                let _ = dashboard.add_favourites(None, settings, |source| {
+                    Ok(source.into_match_source(&settings.discovery))
+                });
+                TickerPhase::Dashboard(Box::new(dashboard))
             }
-        }
-        Ok(HandleInputResponse {
-            should_close,
-            phase: None,
+            _ => return None,
+        };
+        Some(HandleInputResponse {
+            phase: Some(phase),
             stopper: None,
         })
     }
+
+    fn actions(&self) -> Vec<(Action, &'static str)> {
+        vec![
+            (Action::Choose(1), "pick a match from cricinfo"),
+            (Action::Choose(2), "open the cricinfo dashboard"),
+        ]
+    }
 }
 
 struct MatchSelect {
-    source: Source,
+    source: Arc<dyn MatchSource>,
+    matches: Option<Result<Vec<MatchListing>, String>>,
+    receiver: Receiver<Result<Vec<MatchListing>, String>>,
+    list_state: ListState,
 }
 
 impl TickerPhaseTemp for MatchSelect {
     fn update(&mut self) -> Result<(), Error> {
+        if let Ok(matches) = self.receiver.try_recv() {
+            if let Ok(m) = &matches {
+                if !m.is_empty() {
+                    self.list_state.select(Some(0));
+                }
+            }
+            self.matches = Some(matches);
+        }
         Ok(())
     }
 
-    fn draw(
+    fn draw(&mut self, frame: &mut Frame, settings: &Settings) {
+        let title = format!("Matches from {}", self.source.name());
+        let block = Block::default().title(title).borders(Borders::ALL);
+        match &self.matches {
+            Some(Ok(matches)) if !matches.is_empty() => {
+                let items: Vec<ListItem> = matches
+                    .iter()
+                    .map(|m| {
+                        let favourite = settings.favourite_teams.iter().any(|t| m.might_involve(t));
+                        ListItem::new(format!(
+                            "{}[{}] {}  ({})",
+                            if favourite { "\u{2605} " } else { "" },
+                            m.state,
+                            m.display(),
+                            m.category
+                        ))
+                    })
+                    .collect();
+                let widget = List::new(items)
+                    .block(block)
+                    .style(settings.theme.menu)
+                    .highlight_style(settings.theme.selected)
+                    .highlight_symbol("> ");
+                frame.render_stateful_widget(widget, frame.size(), &mut self.list_state);
+            }
+            other => {
+                let text = match other {
+                    None => "Finding matches...".to_string(),
+                    Some(Err(e)) => format!("Failed to list matches: {}", e),
+                    Some(Ok(_)) => "No matches found".to_string(),
+                };
+                let widget = Paragraph::new(text).block(block).style(settings.theme.menu);
+                frame.render_widget(widget, frame.size());
+            }
+        }
+    }
+
+    fn handle_action(
         &mut self,
-        terminal: &mut ratatui::terminal::Terminal<CrosstermBackend<Stdout>>,
+        action: Action,
+        settings: &Settings,
+    ) -> Option<HandleInputResponse> {
+        match action {
+            Action::Down => self.move_selection(1),
+            Action::Up => self.move_selection(-1),
+            Action::Select => {
+                if let Some(listing) = self.selected() {
+                    let wicketick = settings
+                        .polling
+                        .wicketick(self.source.select_match(listing));
+                    let (live_stream, stopper) = settings.live_stream(wicketick);
+                    return Some(HandleInputResponse {
+                        phase: Some(TickerPhase::LiveStream(Box::new(live_stream))),
+                        stopper: Some(stopper),
+                    });
+                }
+            }
+            _ => return None,
+        }
+        Some(HandleInputResponse::default())
+    }
+
+    fn actions(&self) -> Vec<(Action, &'static str)> {
+        vec![
+            (Action::Up, "move up"),
+            (Action::Down, "move down"),
+            (Action::Select, "follow the match"),
+        ]
+    }
+}
+
+impl MatchSelect {
+    // new creates the phase and kicks off listing the source's matches in the background
+    fn new(source: Arc<dyn MatchSource>) -> Self {
+        let (tx, rx) = mpsc::channel(1);
+        let lister = source.clone();
+        tokio::spawn(async move {
+            let matches = lister.list_matches().await.map_err(|e| e.to_string());
+            // if nobody is listening any more there's nothing to do
+            let _ = tx.send(matches).await;
+        });
+        Self {
+            source,
+            matches: None,
+            receiver: rx,
+            list_state: ListState::default(),
+        }
+    }
+
+    fn selected(&self) -> Option<&MatchListing> {
+        match (&self.matches, self.list_state.selected()) {
+            (Some(Ok(matches)), Some(i)) => matches.get(i),
+            _ => None,
+        }
+    }
+
+    // move_selection moves the cursor by offset, clamped to the list
+    fn move_selection(&mut self, offset: i32) {
+        let Some(Ok(matches)) = &self.matches else {
+            return;
+        };
+        if matches.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as i32;
+        let next = (current + offset).clamp(0, matches.len() as i32 - 1);
+        self.list_state.select(Some(next as usize));
+    }
+}
+
+// DashboardRow is one of the matches on the dashboard, with its own poller
+struct DashboardRow {
+    label: String,
+    live_stream: LiveStream,
+    stopper: JoinHandle<()>,
+}
+
+impl DashboardRow {
+    fn new(label: String, source: Arc<dyn MatchSource>, settings: &Settings) -> Self {
+        let (live_stream, stopper) = LiveStream::new(
+            settings.polling.wicketick(source),
+            settings.notifier.clone(),
+        );
+        Self {
+            label,
+            live_stream,
+            stopper,
+        }
+    }
+
+    // line is the compact one line summary of the match
+    fn line(&self, settings: &Settings) -> Line<'static> {
+        let theme = &settings.theme;
+        let Some(summary) = &self.live_stream.wicketick.summary else {
+            return match self.live_stream.error_text(&settings.actions) {
+                Some(error) => Line::from(vec![
+                    Span::raw(format!("{}  ", self.label)),
+                    Span::styled(error, theme.error),
+                ]),
+                None => Line::from(format!("{}  Loading...", self.label)),
+            };
+        };
+        let mut spans = vec![
+            Span::styled(summary.display(), theme.batting(summary)),
+            Span::raw(format!("     {}", summary.display_status())),
+        ];
+        if let Some(cached) = self.live_stream.wicketick.cached_text() {
+            spans.push(Span::raw("     "));
+            spans.push(Span::styled(format!("[{}]", cached), theme.error));
+        } else if self.live_stream.last_error.is_some() {
+            spans.push(Span::raw("     "));
+            spans.push(Span::styled("[STALE]", theme.error));
+        }
+        Line::from(spans)
+    }
+}
+
+// the row going means nobody wants its updates any more
+impl Drop for DashboardRow {
+    fn drop(&mut self) {
+        self.stopper.abort();
+    }
+}
+
+// Dashboard follows several matches at once, a row each
+struct Dashboard {
+    source: Arc<dyn MatchSource>,
+    rows: Vec<DashboardRow>,
+    list_state: ListState,
+    // picking a match to add, from the source's listing
+    adding: Option<MatchSelect>,
+}
+
+impl Dashboard {
+    fn new(source: Arc<dyn MatchSource>) -> Self {
+        Self {
+            source,
+            rows: vec![],
+            list_state: ListState::default(),
+            adding: None,
+        }
+    }
+
+    fn add(&mut self, label: String, source: Arc<dyn MatchSource>, settings: &Settings) {
+        self.rows.push(DashboardRow::new(label, source, settings));
+        self.list_state.select(Some(self.rows.len() - 1));
+    }
+
+    // add_favourites adds a row for each favourite match, bar the one already showing
+    fn add_favourites(
+        &mut self,
+        showing: Option<&String>,
+        settings: &Settings,
+        mut build: impl FnMut(Source) -> Result<Arc<dyn MatchSource>, Error>,
     ) -> Result<(), Error> {
-        let widget = match &self.source {
-            Source::Cricinfo { match_id: _ } => {
-                Paragraph::new("1. pakistan-vs-bangladesh-2nd-test-1442214")
-                    .white()
-                    .on_green()
+        for favourite in &settings.favourite_matches {
+            if Some(favourite) == showing {
+                continue;
             }
-            Source::LocalCricinfo { filename } => Paragraph::new(format!("1. {}", filename))
-                .white()
-                .on_green(),
-            Source::_SomeApi {
-                base_url: _,
-                api_token: _,
-            } => Paragraph::new(format!("Match select not implemented for {}", self.source))
-                .white()
-                .on_green(),
+            let source = Source::Cricinfo {
+                match_id: Some(favourite.clone()),
+            };
+            self.add(source.to_string(), build(source)?, settings);
+        }
+        Ok(())
+    }
+
+    fn remove_selected(&mut self) {
+        let Some(i) = self.list_state.selected() else {
+            return;
         };
-        terminal.draw(|frame| {
-            let area = frame.size();
-            frame.render_widget(widget, area);
-        })?;
+        if i < self.rows.len() {
+            self.rows.remove(i);
+        }
+        self.list_state.select(match self.rows.len() {
+            0 => None,
+            len => Some(i.min(len - 1)),
+        });
+    }
+
+    fn move_selection(&mut self, offset: i32) {
+        if self.rows.is_empty() {
+            return;
+        }
+        let current = self.list_state.selected().unwrap_or(0) as i32;
+        let next = (current + offset).clamp(0, self.rows.len() as i32 - 1);
+        self.list_state.select(Some(next as usize));
+    }
+
+    // handle_adding_action drives the match picker while it's open
+    fn handle_adding_action(&mut self, action: Action, settings: &Settings) {
+        let Some(picker) = &mut self.adding else {
+            return;
+        };
+        match action {
+            Action::Back => self.adding = None,
+            Action::Select => {
+                if let Some(listing) = picker.selected() {
+                    let label = listing.display();
+                    let source = picker.source.select_match(listing);
+                    self.adding = None;
+                    self.add(label, source, settings);
+                }
+            }
+            other => {
+                picker.handle_action(other, settings);
+            }
+        }
+    }
+}
+
+impl TickerPhaseTemp for Dashboard {
+    fn update(&mut self) -> Result<(), Error> {
+        for row in &mut self.rows {
+            row.live_stream.consume_update();
+        }
+        if let Some(picker) = &mut self.adding {
+            picker.update()?;
+        }
         Ok(())
     }
 
-    fn handle_input(&mut self) -> Result<HandleInputResponse, Error> {
-        let mut should_close = false;
-        if let Some(key) = input_key_press()? {
-            match key {
-                KeyCode::Char('q') => should_close = true,
-                KeyCode::Char('1') => {
-                    // TODO un hardcode this
-                    let new_source = Source::Cricinfo {
-                        // match_id: Some("1385695".to_string()),
-                        match_id: Some("1442214".to_string()),
-                        // pakistan-vs-bangladesh-2nd-test-1442214
-                    };
-                    let wicketick = WickeTick::new(new_source, None);
-                    let (live_stream, stopper) = LiveStream::new(wicketick);
-
-                    return Ok(HandleInputResponse {
-                        should_close,
-                        phase: Some(TickerPhase::LiveStream(live_stream)),
+    fn draw(&mut self, frame: &mut Frame, settings: &Settings) {
+        if let Some(picker) = &mut self.adding {
+            return picker.draw(frame, settings);
+        }
+
+        let block = Block::default()
+            .title("Dashboard")
+            .title_bottom(format!("{} for help", settings.actions.keys(Action::Help)))
+            .borders(Borders::ALL);
+        if self.rows.is_empty() {
+            let text = format!(
+                "No matches yet, press {} to add one",
+                settings.actions.keys(Action::Add)
+            );
+            let widget = Paragraph::new(text)
+                .block(block)
+                .style(settings.theme.ticker);
+            frame.render_widget(widget, frame.size());
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| ListItem::new(row.line(settings)))
+            .collect();
+        let widget = List::new(items)
+            .block(block)
+            .style(settings.theme.ticker)
+            .highlight_style(settings.theme.selected)
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(widget, frame.size(), &mut self.list_state);
+    }
+
+    fn handle_action(
+        &mut self,
+        action: Action,
+        settings: &Settings,
+    ) -> Option<HandleInputResponse> {
+        // the picker has the keys while it's open, bar quitting
+        if self.adding.is_some() && action != Action::Quit {
+            self.handle_adding_action(action, settings);
+            return Some(HandleInputResponse::default());
+        }
+        match action {
+            Action::Down => self.move_selection(1),
+            Action::Up => self.move_selection(-1),
+            Action::Add => self.adding = Some(MatchSelect::new(self.source.clone())),
+            Action::Remove => self.remove_selected(),
+            Action::Refresh => {
+                for row in &mut self.rows {
+                    row.live_stream.refresh();
+                }
+            }
+            Action::Select => {
+                if let Some(row) = self.list_state.selected().and_then(|i| self.rows.get(i)) {
+                    // start from what the row already knows, rather than loading again
+                    let (live_stream, stopper) =
+                        settings.live_stream(row.live_stream.wicketick.clone());
+                    return Some(HandleInputResponse {
+                        phase: Some(TickerPhase::LiveStream(Box::new(live_stream))),
                         stopper: Some(stopper),
                     });
                 }
-                _ => {}
             }
+            _ => return None,
         }
-        Ok(HandleInputResponse {
-            should_close,
-            phase: None,
-            stopper: None,
-        })
+        Some(HandleInputResponse::default())
     }
-}
 
-impl MatchSelect {
-    fn new(source: Source) -> Self {
-        Self { source }
+    fn actions(&self) -> Vec<(Action, &'static str)> {
+        if self.adding.is_some() {
+            return vec![
+                (Action::Up, "move up"),
+                (Action::Down, "move down"),
+                (Action::Select, "add the match"),
+                (Action::Back, "stop adding"),
+            ];
+        }
+        vec![
+            (Action::Up, "move up"),
+            (Action::Down, "move down"),
+            (Action::Select, "open the match"),
+            (Action::Add, "add a match"),
+            (Action::Remove, "remove the match"),
+            (Action::Refresh, "refresh every match"),
+        ]
+    }
+
+    fn resume(&mut self) {
+        for row in &mut self.rows {
+            row.live_stream.catch_up();
+        }
     }
 }
 
+// how many of the recent overs the tickers show the balls of
+const RECENT_OVERS_SHOWN: usize = 2;
+const RELAXED_RECENT_OVERS_SHOWN: usize = 4;
+
 // Used to mux the way we lay the summary out in the terminal
 #[derive(Copy, Clone)]
 enum TickerConfiguration {
     MinimalTicker,
-    _RelaxedTicker(Rect),
+    // a full scorecard, with each part of the summary in its own panel
+    RelaxedTicker,
+    // the minimal ticker with the ball by ball commentary scrolling beneath it
+    Commentary,
+}
+
+impl From<TickerLayout> for TickerConfiguration {
+    fn from(layout: TickerLayout) -> Self {
+        match layout {
+            TickerLayout::Minimal => TickerConfiguration::MinimalTicker,
+            TickerLayout::Relaxed => TickerConfiguration::RelaxedTicker,
+            TickerLayout::Commentary => TickerConfiguration::Commentary,
+        }
+    }
 }
 
 // TODO rename
@@ -428,105 +1257,570 @@ struct LiveStream {
     wicketick: WickeTick,
     // wicketick_copy: Arc<Mutex<WickeTick>>,
     configuration: TickerConfiguration,
+    // show_details adds the fall of wickets beneath the ticker
+    show_details: bool,
+    // every ball of commentary seen since we started, as each poll only has the latest few
+    commentary: Commentary,
+    // how many lines the commentary pane is scrolled down by
+    commentary_scroll: u16,
     // sender: Sender<SimpleSummary>,
-    receiver: Receiver<SimpleSummary>,
+    receiver: Receiver<PollUpdate>,
+    // the error from the latest poll, cleared by the next successful one
+    last_error: Option<String>,
+    // how many polls in a row have failed
+    retries: u32,
+    // the poller has stopped trying after too many failures
+    gave_up: bool,
+    notifier: Notifier,
+    // refresher asks the poller for an out of band refetch
+    refresher: Sender<RefreshReply>,
+    pending_refresh: Option<oneshot::Receiver<Result<(), String>>>,
+    // how the last refresh we asked for went
+    refresh_outcome: Option<Result<(), String>>,
+    // summaries fetched before this only catch up without notifying, and the first one
+    // fetched after it is the new baseline for events
+    quiet_until: Option<Instant>,
+}
+
+// PollUpdate is what the poller sends the ticker after each fetch
+enum PollUpdate {
+    Summary {
+        summary: Box<SimpleSummary>,
+        fetched_at: Instant,
+    },
+    Failed {
+        error: String,
+        retries: u32,
+    },
+    // too many failures in a row, the poller won't try again unless asked to
+    GaveUp {
+        error: String,
+        retries: u32,
+    },
 }
 
+// RefreshReply is how the poller tells the ticker how a forced refresh went
+type RefreshReply = oneshot::Sender<Result<(), String>>;
+
 impl TickerPhaseTemp for LiveStream {
     fn update(&mut self) -> Result<(), Error> {
+        if let Some(pending) = &mut self.pending_refresh {
+            match pending.try_recv() {
+                Ok(outcome) => {
+                    self.refresh_outcome = Some(outcome);
+                    self.pending_refresh = None;
+                }
+                Err(oneshot::error::TryRecvError::Empty) => {}
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    self.refresh_outcome = Some(Err("poller stopped".to_string()));
+                    self.pending_refresh = None;
+                }
+            }
+        }
+
         // here we want to try receiving from our channel with tokio select
         // which lets us recognise that nothing has changed.
         match self.configuration {
             TickerConfiguration::MinimalTicker => {}
-            TickerConfiguration::_RelaxedTicker(_size) => {}
+            TickerConfiguration::RelaxedTicker => {}
+            TickerConfiguration::Commentary => {}
         }
         Ok(())
     }
 
-    fn draw(
-        &mut self,
-        terminal: &mut ratatui::terminal::Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<(), Error> {
-        let widget = match self.configuration {
+    fn draw(&mut self, frame: &mut Frame, settings: &Settings) {
+        match self.configuration {
             TickerConfiguration::MinimalTicker => {
-                if let Some(summary) = &self.wicketick.summary {
-                    let basic_text = summary.display();
-                    let active_player_text = format!(
-                        "{}     {}",
-                        summary.active_players.display_batters(),
-                        summary.active_players.display_bowlers()
-                    );
-                    let text = format!("{}          {}", basic_text, active_player_text);
-                    Paragraph::new(text.clone()).white().on_black()
-                } else {
-                    Paragraph::new("Loading...").white().on_black()
-                }
-            }
-            TickerConfiguration::_RelaxedTicker(_size) => {
-                Paragraph::new("Relaxed ticker not implemented")
-                    .white()
-                    .on_blue()
+                let widget =
+                    Paragraph::new(self.minimal_lines(settings)).style(settings.theme.ticker);
+                frame.render_widget(widget, frame.size());
             }
-        };
-        terminal.draw(|frame| {
-            let area = frame.size();
-            frame.render_widget(widget, area);
-        })?;
-        Ok(())
+            TickerConfiguration::RelaxedTicker => self.draw_relaxed(frame, settings),
+            TickerConfiguration::Commentary => self.draw_commentary(frame, settings),
+        }
     }
 
-    fn handle_input(&mut self) -> Result<HandleInputResponse, Error> {
-        let mut should_close: bool = false;
-        if let Some(key) = input_key_press()? {
-            match key {
-                KeyCode::Char('q') => should_close = true,
-                KeyCode::Char('r') => {
-                    // TODO kick off a task
+    fn handle_action(
+        &mut self,
+        action: Action,
+        _settings: &Settings,
+    ) -> Option<HandleInputResponse> {
+        match action {
+            Action::Details => self.show_details = !self.show_details,
+            Action::Layout => {
+                self.configuration = match self.configuration {
+                    TickerConfiguration::RelaxedTicker => TickerConfiguration::MinimalTicker,
+                    _ => TickerConfiguration::RelaxedTicker,
                 }
-                // KeyCode::Char('r') => self.wicketick.refresh()?, // TODO here is where we want to spawn a new task
-                _ => {}
             }
+            Action::Commentary => {
+                self.configuration = match self.configuration {
+                    TickerConfiguration::Commentary => TickerConfiguration::MinimalTicker,
+                    _ => TickerConfiguration::Commentary,
+                }
+            }
+            Action::Down => self.commentary_scroll = self.commentary_scroll.saturating_add(1),
+            Action::Up => self.commentary_scroll = self.commentary_scroll.saturating_sub(1),
+            Action::Refresh => self.refresh(),
+            _ => return None,
         }
-        Ok(HandleInputResponse {
-            should_close,
-            phase: None,
-            stopper: None,
-        })
+        Some(HandleInputResponse::default())
+    }
+
+    fn actions(&self) -> Vec<(Action, &'static str)> {
+        vec![
+            (Action::Refresh, "refresh now"),
+            (
+                Action::Layout,
+                "switch between the ticker and the scorecard",
+            ),
+            (Action::Commentary, "show or hide the commentary"),
+            (Action::Details, "show or hide the fall of wickets"),
+            (Action::Up, "scroll the commentary up"),
+            (Action::Down, "scroll the commentary down"),
+        ]
+    }
+
+    fn resume(&mut self) {
+        self.catch_up();
     }
 }
 
 // TODO could genericify this too
 impl LiveStream {
-    fn start_poll(&mut self, sender: Sender<SimpleSummary>) -> JoinHandle<()> {
-        eprintln!("starting poll");
+    // start_poll refetches every poll interval, or straight away when asked to through
+    // refresh_requests, replying on the request's channel once the refetch is done
+    fn start_poll(
+        &mut self,
+        sender: Sender<PollUpdate>,
+        mut refresh_requests: Receiver<RefreshReply>,
+    ) -> JoinHandle<()> {
         let w = self.wicketick.clone();
         let mut loop_count = 0;
-        let h = tokio::spawn(async move {
+        tokio::spawn(async move {
+            let mut reply: Option<RefreshReply> = None;
+            // consecutive failed fetches, reset by a successful one
+            let mut retries = 0;
+            // the interval for the last known state of the match, backed off from on failure
+            let mut interval = w.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL);
             loop {
-                match w.refetch().await {
+                let mut keep_polling = w.source.should_poll();
+                let mut delay = interval;
+                let (update, outcome) = match w.refetch().await {
                     Ok(mut summary) => {
                         summary.debug_string = format!("(Ticks: {})", loop_count);
                         loop_count += 1;
-                        sender.send(summary.clone()).await.unwrap();
+                        retries = 0;
+                        match w.poll_interval_for(&summary.status) {
+                            Some(next) => {
+                                interval = next;
+                                delay = next;
+                            }
+                            None => keep_polling = false,
+                        }
+                        let update = PollUpdate::Summary {
+                            summary: Box::new(summary),
+                            fetched_at: Instant::now(),
+                        };
+                        (update, Ok(()))
                     }
                     Err(e) => {
-                        eprintln!("Oh no: {}", e);
+                        retries += 1;
+                        let error = e.to_string();
+                        if w.backoff.should_give_up(retries) {
+                            // only a forced refresh will try again now
+                            keep_polling = false;
+                            (
+                                PollUpdate::GaveUp {
+                                    error: error.clone(),
+                                    retries,
+                                },
+                                Err(error),
+                            )
+                        } else {
+                            keep_polling = true;
+                            delay = w.backoff.delay(interval, retries);
+                            (
+                                PollUpdate::Failed {
+                                    error: error.clone(),
+                                    retries,
+                                },
+                                Err(error),
+                            )
+                        }
                     }
+                };
+                if sender.send(update).await.is_err() {
+                    // the ticker has gone away, so there's nobody to poll for
+                    break;
                 }
-                if let Some(interval) = w.poll_interval {
-                    tokio::time::sleep(interval.clone()).await;
+                if let Some(reply) = reply.take() {
+                    // the phase may have moved on, in which case nobody needs to know
+                    let _ = reply.send(outcome);
+                }
+
+                if keep_polling {
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        request = refresh_requests.recv() => match request {
+                            Some(request) => reply = Some(request),
+                            None => break,
+                        },
+                    }
                 } else {
-                    tokio::time::sleep(DEFAULT_POLL_INTERVAL).await;
+                    // nothing will change by itself, but a refresh can still be asked for
+                    match refresh_requests.recv().await {
+                        Some(request) => reply = Some(request),
+                        None => break,
+                    }
                 }
             }
-        });
-        h
+        })
     }
 
     fn consume_update(&mut self) {
-        if let Ok(summary) = self.receiver.try_recv() {
-            self.wicketick.summary = Some(summary);
+        if let Ok(update) = self.receiver.try_recv() {
+            self.apply_update(update);
+        }
+    }
+
+    // catch_up takes whatever the poller sent while the phase sat in the history and
+    // refetches, as the poller was stuck waiting for us. Whoever was open meanwhile
+    // notified about the match, so nothing until the refetch gets notified again.
+    fn catch_up(&mut self) {
+        self.quiet_until = Some(Instant::now());
+        while let Ok(update) = self.receiver.try_recv() {
+            self.apply_update(update);
+        }
+        self.refresh();
+    }
+
+    // wait_for_update blocks until the poller has something, false if it has stopped
+    async fn wait_for_update(&mut self) -> bool {
+        match self.receiver.recv().await {
+            Some(update) => {
+                self.apply_update(update);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn apply_update(&mut self, update: PollUpdate) {
+        match update {
+            PollUpdate::Summary {
+                summary,
+                fetched_at,
+            } => {
+                // a new match, e.g. following a team onto their next one, starts afresh
+                if let Some(previous) = &self.wicketick.summary {
+                    if !previous.same_match(&summary) {
+                        self.commentary = Commentary::default();
+                        self.commentary_scroll = 0;
+                        self.wicketick.summary = None;
+                    }
+                }
+                let quiet = match self.quiet_until {
+                    Some(until) => {
+                        if fetched_at >= until {
+                            self.quiet_until = None;
+                        }
+                        true
+                    }
+                    None => false,
+                };
+                // only compare against polled summaries, a cached one could be hours old
+                if let (Some(previous), Some(_)) =
+                    (&self.wicketick.summary, self.wicketick.last_refresh)
+                {
+                    if !quiet && !self.notifier.is_empty() {
+                        self.notifier
+                            .notify(&events::diff(previous, &summary), &summary);
+                    }
+                }
+                self.commentary.merge(&summary.commentary);
+                self.wicketick.set_summary(*summary);
+                self.wicketick.last_refresh = Some(fetched_at);
+                self.last_error = None;
+                self.retries = 0;
+                self.gave_up = false;
+            }
+            // keep showing the last good summary, but flag that it's out of date
+            PollUpdate::Failed { error, retries } => {
+                self.last_error = Some(error);
+                self.retries = retries;
+                self.gave_up = false;
+            }
+            PollUpdate::GaveUp { error, retries } => {
+                self.last_error = Some(error);
+                self.retries = retries;
+                self.gave_up = true;
+            }
+        }
+    }
+
+    // error_text describes why the summary isn't up to date, if it isn't
+    fn error_text(&self, actions: &ActionMap) -> Option<String> {
+        let error = self.last_error.as_ref()?;
+        if self.gave_up {
+            return Some(format!(
+                "GAVE UP after {} failures: {} (press {} to retry)",
+                self.retries,
+                error,
+                actions.keys(Action::Refresh)
+            ));
+        }
+        let badge = match self.wicketick.summary {
+            Some(_) => "STALE",
+            None => "FAILED",
+        };
+        Some(format!("{}: {} (retry {})", badge, error, self.retries))
+    }
+
+    // refresh asks the poller to refetch now, rather than waiting for the next poll
+    fn refresh(&mut self) {
+        if self.pending_refresh.is_some() {
+            return;
+        }
+        let (tx, rx) = oneshot::channel();
+        match self.refresher.try_send(tx) {
+            Ok(()) => self.pending_refresh = Some(rx),
+            Err(e) => self.refresh_outcome = Some(Err(format!("couldn't refresh: {}", e))),
+        }
+    }
+
+    // refresh_text describes the state of the last refresh we asked for, if any,
+    // and how long it's been since the summary was last refreshed
+    fn refresh_text(&self) -> String {
+        let since = match (self.wicketick.last_refresh, self.wicketick.cached_text()) {
+            (Some(at), _) => format!("last refresh {}s ago", at.elapsed().as_secs()),
+            (None, Some(cached)) => cached,
+            (None, None) => "not refreshed yet".to_string(),
         };
+        match (&self.pending_refresh, &self.refresh_outcome) {
+            (Some(_), _) => "refreshing\u{2026}".to_string(),
+            (None, Some(Err(e))) => format!("refresh failed: {} ({})", e, since),
+            (None, Some(Ok(()))) => format!("refreshed ({})", since),
+            (None, None) => since,
+        }
+    }
+
+    // minimal_lines is the whole summary squashed onto as few lines as possible
+    fn minimal_lines(&self, settings: &Settings) -> Vec<Line<'static>> {
+        let theme = &settings.theme;
+        let Some(summary) = &self.wicketick.summary else {
+            return vec![self.placeholder_line(settings)];
+        };
+        let gap = || Span::raw("     ");
+        let partnership_text = match &summary.partnership {
+            Some(partnership) => partnership.display(),
+            None => "".to_string(),
+        };
+        let mut spans = vec![
+            Span::styled(summary.display(), theme.batting(summary)),
+            Span::raw("          "),
+        ];
+        spans.extend(theme.batters(summary));
+        spans.extend([
+            gap(),
+            Span::raw(summary.active_players.display_bowlers()),
+            gap(),
+            Span::raw(partnership_text),
+            gap(),
+        ]);
+        spans.extend(theme.recent_overs(summary, RECENT_OVERS_SHOWN));
+        spans.extend([
+            gap(),
+            Span::raw(summary.display_status()),
+            gap(),
+            Span::raw(format!("[{}]", self.refresh_text())),
+        ]);
+        if let Some(error) = self.error_text(&settings.actions) {
+            spans.push(gap());
+            spans.push(Span::styled(format!("[{}]", error), theme.error));
+        }
+
+        let mut lines = vec![Line::from(spans)];
+        if self.show_details {
+            lines.push(Line::default());
+            lines.push(Line::from("Fall of wickets:"));
+            for fow in &summary.fall_of_wickets {
+                lines.push(Line::from(format!("  {}", fow.display_detailed())));
+            }
+        }
+        lines
+    }
+
+    // placeholder_line is what to show until there's a summary
+    fn placeholder_line(&self, settings: &Settings) -> Line<'static> {
+        match self.error_text(&settings.actions) {
+            Some(error) => Line::styled(error, settings.theme.error),
+            None => Line::from("Loading..."),
+        }
+    }
+
+    fn draw_relaxed(&self, frame: &mut Frame, settings: &Settings) {
+        let theme = &settings.theme;
+        let Some(summary) = &self.wicketick.summary else {
+            let widget = Paragraph::new(self.placeholder_line(settings)).style(theme.panel);
+            frame.render_widget(widget, frame.size());
+            return;
+        };
+
+        let error = self.error_text(&settings.actions);
+        // the status panel grows a line to fit the error
+        let status_height = if error.is_some() { 4 } else { 3 };
+        let [header_area, players_area, situation_area, status_area, details_area] =
+            Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Length(5),
+                Constraint::Length(3),
+                Constraint::Length(status_height),
+                Constraint::Min(0),
+            ])
+            .areas(frame.size());
+        let [batters_area, bowlers_area] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(players_area);
+        let [partnership_area, recent_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+                .areas(situation_area);
+
+        let panel = |title: &'static str| Block::default().title(title).borders(Borders::ALL);
+
+        let header = Paragraph::new(Line::styled(summary.display(), theme.batting(summary)))
+            .block(panel("Score"))
+            .style(theme.panel)
+            .bold();
+        frame.render_widget(header, header_area);
+
+        let batter_rows = summary.active_players.batters().into_iter().map(|b| {
+            let name = match b.on_strike() {
+                true => format!("{}*", b.name()),
+                false => b.name().to_string(),
+            };
+            Row::new(vec![
+                Cell::from(name).style(theme.batter(b)),
+                Cell::from(b.runs().to_string()),
+                Cell::from(b.balls_faced().to_string()),
+                Cell::from(b.fours().to_string()),
+                Cell::from(b.sixes().to_string()),
+                Cell::from(format!("{:.2}", b.strike_rate())),
+            ])
+        });
+        let batters = Table::new(
+            batter_rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(4),
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(7),
+            ],
+        )
+        .header(Row::new(vec!["Batter", "R", "B", "4s", "6s", "SR"]).bold())
+        .block(panel("Batting").border_style(theme.batting(summary)))
+        .style(theme.panel);
+        frame.render_widget(batters, batters_area);
+
+        let bowler_rows = summary.active_players.bowlers().into_iter().map(|b| {
+            Row::new(vec![
+                b.name().to_string(),
+                b.overs().display(),
+                b.maidens().to_string(),
+                b.runs_conceded().to_string(),
+                b.wickets().to_string(),
+                format!("{:.2}", b.economy()),
+            ])
+        });
+        let bowlers = Table::new(
+            bowler_rows,
+            [
+                Constraint::Min(12),
+                Constraint::Length(5),
+                Constraint::Length(3),
+                Constraint::Length(4),
+                Constraint::Length(3),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(vec!["Bowler", "O", "M", "R", "W", "Econ"]).bold())
+        .block(panel("Bowling").border_style(theme.bowling(summary)))
+        .style(theme.panel);
+        frame.render_widget(bowlers, bowlers_area);
+
+        let partnership_text = match &summary.partnership {
+            Some(partnership) => partnership.display(),
+            None => "".to_string(),
+        };
+        let partnership = Paragraph::new(partnership_text)
+            .block(panel("Partnership"))
+            .style(theme.panel);
+        frame.render_widget(partnership, partnership_area);
+
+        let recent = Paragraph::new(Line::from(
+            theme.recent_overs(summary, RELAXED_RECENT_OVERS_SHOWN),
+        ))
+        .block(panel("Recent overs"))
+        .style(theme.panel);
+        frame.render_widget(recent, recent_area);
+
+        let mut status_lines = vec![Line::from(summary.display_status())];
+        if let Some(error) = error {
+            status_lines.push(Line::styled(error, theme.error));
+        }
+        let status = Paragraph::new(status_lines)
+            .block(panel("Status").title_bottom(self.refresh_text()))
+            .style(theme.panel);
+        frame.render_widget(status, status_area);
+
+        let mut details = vec![Line::from(format!(
+            "Extras: {}",
+            summary.current_innings.extras.display()
+        ))];
+        if self.show_details {
+            details.push(Line::from(summary.display_fall_of_wickets()));
+        }
+        let details = Paragraph::new(details)
+            .block(panel("Details"))
+            .wrap(Wrap { trim: true })
+            .style(theme.panel);
+        frame.render_widget(details, details_area);
+    }
+
+    fn draw_commentary(&self, frame: &mut Frame, settings: &Settings) {
+        // the score gets as many lines as it wraps onto, leaving at least half the screen
+        // for the commentary
+        let area = frame.size();
+        let lines = self.minimal_lines(settings);
+        let width = area.width.max(1) as usize;
+        let wrapped: usize = lines.iter().map(|l| l.width().div_ceil(width).max(1)).sum();
+        let score_height = (wrapped as u16).clamp(1, (area.height / 2).max(1));
+        let [score_area, commentary_area] =
+            Layout::vertical([Constraint::Length(score_height), Constraint::Min(0)]).areas(area);
+
+        let score = Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .style(settings.theme.ticker);
+        frame.render_widget(score, score_area);
+
+        // newest first, so the latest ball is always at the top when not scrolled
+        let lines: Vec<Line> = self
+            .commentary
+            .newest_first()
+            .map(|ball| Line::styled(ball.display(), settings.theme.commentary(ball)))
+            .collect();
+        let commentary = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(format!("Commentary ({} balls)", self.commentary.len()))
+                    .borders(Borders::ALL),
+            )
+            .wrap(Wrap { trim: true })
+            .scroll((self.commentary_scroll, 0))
+            .style(settings.theme.ticker);
+        frame.render_widget(commentary, commentary_area);
     }
 }
 
@@ -534,16 +1828,31 @@ impl LiveStream {
 
 impl LiveStream {
     // new creates and returns a new phase, also starts the poller, and returns the JoinHandle needed to abort the poller
-    fn new(wicketick: WickeTick) -> (Self, JoinHandle<()>) {
+    fn new(mut wicketick: WickeTick, notifier: Notifier) -> (Self, JoinHandle<()>) {
         let (tx, rx) = mpsc::channel(1);
+        if wicketick.summary.is_none() {
+            wicketick.load_cached();
+        }
+        let (refresh_tx, refresh_rx) = mpsc::channel(1);
 
         let mut ls = LiveStream {
             wicketick,
             configuration: TickerConfiguration::MinimalTicker,
+            show_details: false,
+            commentary: Commentary::default(),
+            commentary_scroll: 0,
             receiver: rx,
+            last_error: None,
+            retries: 0,
+            gave_up: false,
+            notifier,
+            refresher: refresh_tx,
+            pending_refresh: None,
+            refresh_outcome: None,
+            quiet_until: None,
         };
 
-        let jh = ls.start_poll(tx);
+        let jh = ls.start_poll(tx, refresh_rx);
 
         (ls, jh)
     }
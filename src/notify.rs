@@ -0,0 +1,119 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::wicketick::events::MatchEvent;
+use crate::wicketick::SimpleSummary;
+
+// Filter picks out which events someone wants to hear about
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Filter {
+    // event kinds, e.g. wicket or boundary, empty for all of them
+    pub events: Vec<String>,
+    // team ids, names or abbreviations, empty for every team
+    pub teams: Vec<String>,
+}
+
+impl Filter {
+    pub fn matches(&self, event: &MatchEvent, summary: &SimpleSummary) -> bool {
+        let event_matches = self.events.is_empty() || self.events.iter().any(|e| e == event.kind());
+        let team_matches = self.teams.is_empty()
+            || event.team_ids().iter().any(|id| {
+                let team = summary.match_summary.team(id);
+                self.teams.iter().any(|wanted| {
+                    wanted.eq_ignore_ascii_case(id) || team.is_some_and(|t| t.goes_by(wanted))
+                })
+            });
+        event_matches && team_matches
+    }
+}
+
+// Hook is a shell command to run for every event that gets through its own filter.
+// It gets the event in WICKETICK_* environment variables and as json on stdin.
+#[derive(Clone, Debug)]
+pub struct Hook {
+    pub command: String,
+    pub filter: Filter,
+}
+
+impl Hook {
+    pub fn new(command: String, filter: Filter) -> Self {
+        Self { command, filter }
+    }
+
+    fn run(&self, event: &MatchEvent, summary: &SimpleSummary) {
+        let child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env("WICKETICK_EVENT", event.kind())
+            .env("WICKETICK_MESSAGE", event.display())
+            .env("WICKETICK_TEAMS", event.team_ids().join(","))
+            .env("WICKETICK_SCORE", summary.display())
+            .stdin(Stdio::piped())
+            // anything it prints would land on top of the ticker
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let Ok(mut child) = child else {
+            return;
+        };
+
+        let input = json!({
+            "event": event,
+            "message": event.display(),
+            "score": summary.display(),
+        });
+        // don't hold the ticker up on a slow hook
+        std::thread::spawn(move || {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = stdin.write_all(input.to_string().as_bytes());
+            }
+            let _ = child.wait();
+        });
+    }
+}
+
+// Notifier tells the desktop and any hooks about match events
+#[derive(Clone, Debug, Default)]
+pub struct Notifier {
+    // desktop notifications over d-bus, none to not send any
+    pub desktop: Option<Filter>,
+    pub hooks: Vec<Hook>,
+}
+
+impl Notifier {
+    pub fn is_empty(&self) -> bool {
+        self.desktop.is_none() && self.hooks.is_empty()
+    }
+
+    pub fn notify(&self, events: &[MatchEvent], summary: &SimpleSummary) {
+        for event in events {
+            if let Some(filter) = &self.desktop {
+                if filter.matches(event, summary) {
+                    desktop_notification(event, summary);
+                }
+            }
+            for hook in &self.hooks {
+                if hook.filter.matches(event, summary) {
+                    hook.run(event, summary);
+                }
+            }
+        }
+    }
+}
+
+fn desktop_notification(event: &MatchEvent, summary: &SimpleSummary) {
+    let title = event.display();
+    let body = summary.display();
+    // talking to d-bus blocks, and there may be no notification daemon at all
+    std::thread::spawn(move || {
+        let _ = notify_rust::Notification::new()
+            .appname("wicketick")
+            .summary(&title)
+            .body(&body)
+            .show();
+    });
+}
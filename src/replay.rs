@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::errors::Error;
+
+// A session is a directory of raw responses for one match, each named for the unix
+// time in milliseconds it was received at, e.g. 1720815300000.json. Recording puts
+// each match's session in its own directory named for the match id.
+
+// Recorder saves raw responses into a session directory as they come in
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    dir: PathBuf,
+}
+
+impl Recorder {
+    // new creates the session directory if it isn't there already
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self, Error> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    // record saves the body into the match's own session, so matches recorded
+    // together can still be replayed one at a time
+    pub fn record(&self, match_id: &str, body: &str) -> Result<(), Error> {
+        let dir = self.dir.join(match_id);
+        std::fs::create_dir_all(&dir)?;
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or_default();
+        std::fs::write(dir.join(format!("{}.json", millis)), body)?;
+        Ok(())
+    }
+}
+
+// ReplaySpeed is how fast to move through a recorded session
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReplaySpeed {
+    // as fast as it was recorded, scaled by the given factor
+    Timed(f64),
+    // one response further on every fetch
+    Stepped,
+}
+
+// Session is the responses recorded in a session directory, oldest first
+#[derive(Clone, Debug)]
+pub struct Session {
+    frames: Vec<(Duration, PathBuf)>,
+}
+
+impl Session {
+    // load reads the session in dir, or in its only match directory when dir is
+    // where a recording was made
+    pub fn load(dir: &Path) -> Result<Self, Error> {
+        let mut frames = vec![];
+        let mut matches = vec![];
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                matches.push(path);
+            } else if path.extension().is_some_and(|e| e == "json") {
+                // anything else in there isn't ours
+                let stem = path.file_stem().and_then(|s| s.to_str());
+                if let Some(millis) = stem.and_then(|s| s.parse::<u64>().ok()) {
+                    frames.push((Duration::from_millis(millis), path));
+                }
+            }
+        }
+        if frames.is_empty() && matches.len() == 1 {
+            return Self::load(&matches[0]);
+        }
+        if frames.is_empty() && matches.len() > 1 {
+            return Err(Error::Todo(format!(
+                "{} has recordings of {} matches, replay one of the directories in it",
+                dir.display(),
+                matches.len()
+            )));
+        }
+        if frames.is_empty() {
+            return Err(Error::EmptySession(dir.display().to_string()));
+        }
+        frames.sort();
+        Ok(Self { frames })
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // frame_at is the index of the last response received by `elapsed` into the session
+    pub fn frame_at(&self, elapsed: Duration) -> usize {
+        let start = self.frames[0].0;
+        self.frames
+            .iter()
+            .rposition(|(t, _)| t.saturating_sub(start) <= elapsed)
+            .unwrap_or(0)
+    }
+
+    pub fn read(&self, frame: usize) -> Result<String, Error> {
+        let (_, path) = &self.frames[frame.min(self.frames.len() - 1)];
+        Ok(std::fs::read_to_string(path)?)
+    }
+}
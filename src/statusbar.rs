@@ -0,0 +1,290 @@
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::errors::Error;
+use crate::wicketick::{MatchStatus, SimpleSummary, SummaryRecord};
+
+// StatusBarFormat is the kind of status bar we're writing lines for
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum StatusBarFormat {
+    // tmux status-right, with #[...] style codes
+    Tmux,
+    // the i3bar/swaybar json protocol
+    I3bar,
+    // waybar custom module json, one object per line
+    Waybar,
+    // the whole summary as json, one object per line, for other programs
+    Ndjson,
+}
+
+// StatusBar turns summaries into lines in one of the status bar formats
+pub struct StatusBar {
+    format: StatusBarFormat,
+    // i3bar wants a comma between every status line but not before the first
+    lines_written: usize,
+}
+
+impl StatusBar {
+    pub fn new(format: StatusBarFormat) -> Self {
+        Self {
+            format,
+            lines_written: 0,
+        }
+    }
+
+    // header is anything the bar needs before the first line
+    pub fn header(&self) -> Option<String> {
+        match self.format {
+            StatusBarFormat::I3bar => Some("{\"version\":1}\n[".to_string()),
+            _ => None,
+        }
+    }
+
+    // line is the next line to write for the latest summary, and the last error if
+    // the summary is out of date
+    pub fn line(
+        &mut self,
+        summary: Option<&SimpleSummary>,
+        error: Option<&str>,
+    ) -> Result<String, Error> {
+        let line = match self.format {
+            StatusBarFormat::Tmux => tmux_line(summary, error),
+            StatusBarFormat::I3bar => {
+                let mut block = json!({
+                    "name": "wicketick",
+                    "full_text": full_text(summary, error),
+                    "short_text": headline(summary, error),
+                });
+                // leave the bar's own colour alone unless there's something to flag
+                if let Some(colour) = i3bar_colour(summary, error) {
+                    block["color"] = json!(colour);
+                }
+                let block = json!([block]);
+                match self.lines_written {
+                    0 => block.to_string(),
+                    _ => format!(",{}", block),
+                }
+            }
+            StatusBarFormat::Waybar => json!({
+                "text": headline(summary, error),
+                "tooltip": tooltip(summary, error),
+                "class": class(summary, error),
+            })
+            .to_string(),
+            StatusBarFormat::Ndjson => {
+                let record = SummaryRecord::new(summary.cloned(), error.map(|e| e.to_string()));
+                serde_json::to_string(&record)?
+            }
+        };
+        self.lines_written += 1;
+        Ok(line)
+    }
+}
+
+// headline is the shortest useful thing to show, the score
+fn headline(summary: Option<&SimpleSummary>, error: Option<&str>) -> String {
+    match (summary, error) {
+        (Some(summary), _) => summary.display(),
+        (None, Some(error)) => format!("wicketick: {}", error),
+        (None, None) => "wicketick: loading".to_string(),
+    }
+}
+
+fn batters(summary: &SimpleSummary) -> String {
+    summary
+        .active_players
+        .batters()
+        .iter()
+        .map(|b| b.display())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn bowlers(summary: &SimpleSummary) -> String {
+    summary
+        .active_players
+        .bowlers()
+        .iter()
+        .map(|b| b.display())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// full_text is the score, who's batting and the state of play
+fn full_text(summary: Option<&SimpleSummary>, error: Option<&str>) -> String {
+    let Some(summary) = summary else {
+        return headline(summary, error);
+    };
+    let mut parts = vec![
+        summary.display(),
+        batters(summary),
+        summary.display_status(),
+    ];
+    if error.is_some() {
+        parts.push("STALE".to_string());
+    }
+    parts.retain(|p| !p.is_empty());
+    parts.join(" | ")
+}
+
+fn tooltip(summary: Option<&SimpleSummary>, error: Option<&str>) -> String {
+    let mut lines = vec![];
+    if let Some(summary) = summary {
+        lines.push(summary.display_status());
+        lines.push(batters(summary));
+        lines.push(bowlers(summary));
+        if let Some(partnership) = &summary.partnership {
+            lines.push(partnership.display());
+        }
+        lines.push(summary.display_recent_overs(2));
+    }
+    if let Some(error) = error {
+        lines.push(format!("Last update failed: {}", error));
+    }
+    lines.retain(|l| !l.is_empty());
+    lines.join("\n")
+}
+
+// class is for styling the waybar module from css
+fn class(summary: Option<&SimpleSummary>, error: Option<&str>) -> &'static str {
+    if error.is_some() {
+        return "stale";
+    }
+    match summary.map(|s| &s.status) {
+        None => "loading",
+        Some(MatchStatus::Scheduled { .. }) => "scheduled",
+        Some(MatchStatus::Live) => "live",
+        Some(MatchStatus::InningsBreak | MatchStatus::Stumps | MatchStatus::Break(_)) => "break",
+        Some(MatchStatus::RainDelay) => "rain",
+        Some(MatchStatus::Result { .. }) => "result",
+    }
+}
+
+fn i3bar_colour(summary: Option<&SimpleSummary>, error: Option<&str>) -> Option<&'static str> {
+    match class(summary, error) {
+        "stale" => Some("#ff5555"),
+        "result" => Some("#50fa7b"),
+        "break" | "rain" => Some("#f1fa8c"),
+        _ => None,
+    }
+}
+
+// tmux_line is the full text with the score in bold and the status dimmed
+fn tmux_line(summary: Option<&SimpleSummary>, error: Option<&str>) -> String {
+    // a lone # would start a style code
+    let escape = |s: String| s.replace('#', "##");
+    let Some(summary) = summary else {
+        return escape(headline(summary, error));
+    };
+    let mut line = format!("#[bold]{}#[nobold]", escape(summary.display()));
+    let batters = batters(summary);
+    if !batters.is_empty() {
+        line.push_str(&format!(" {}", escape(batters)));
+    }
+    line.push_str(&format!(
+        " #[dim]{}#[nodim]",
+        escape(summary.display_status())
+    ));
+    if error.is_some() {
+        line.push_str(" #[fg=red]STALE#[default]");
+    }
+    line
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cricinfo;
+    use crate::wicketick::SCHEMA_VERSION;
+
+    fn example() -> SimpleSummary {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/example_matches/example_match.json"
+        );
+        cricinfo::load_match_summary(path.to_string()).unwrap()
+    }
+
+    #[test]
+    fn tmux_escapes_hashes() {
+        let mut bar = StatusBar::new(StatusBarFormat::Tmux);
+        assert_eq!(
+            bar.line(None, Some("bad #ff0000 response")).unwrap(),
+            "wicketick: bad ##ff0000 response"
+        );
+
+        let line = bar.line(Some(&example()), Some("timed out")).unwrap();
+        assert!(
+            line.starts_with("#[bold]WORCS 187/6 (17) \u{2014} BEARS 29/3 (4.2) need 159#[nobold]")
+        );
+        assert!(line.ends_with(" #[fg=red]STALE#[default]"));
+    }
+
+    #[test]
+    fn i3bar_commas_between_lines_only() {
+        let mut bar = StatusBar::new(StatusBarFormat::I3bar);
+        assert_eq!(bar.header().unwrap(), "{\"version\":1}\n[");
+
+        let first = bar.line(Some(&example()), None).unwrap();
+        let blocks: serde_json::Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(blocks[0]["name"], "wicketick");
+        // live play leaves the bar's own colour alone
+        assert!(blocks[0].get("color").is_none());
+
+        let second = bar.line(Some(&example()), Some("timed out")).unwrap();
+        let blocks: serde_json::Value =
+            serde_json::from_str(second.strip_prefix(',').unwrap()).unwrap();
+        assert_eq!(blocks[0]["color"], "#ff5555");
+    }
+
+    #[test]
+    fn waybar_classes() {
+        let class_of = |summary: Option<&SimpleSummary>, error: Option<&str>| {
+            let mut bar = StatusBar::new(StatusBarFormat::Waybar);
+            let line: serde_json::Value =
+                serde_json::from_str(&bar.line(summary, error).unwrap()).unwrap();
+            line["class"].as_str().unwrap().to_string()
+        };
+        let mut summary = example();
+        assert_eq!(class_of(None, None), "loading");
+        assert_eq!(class_of(Some(&summary), None), "live");
+        assert_eq!(class_of(Some(&summary), Some("timed out")), "stale");
+        summary.status = MatchStatus::Break("Tea".to_string());
+        assert_eq!(class_of(Some(&summary), None), "break");
+        summary.status = MatchStatus::RainDelay;
+        assert_eq!(class_of(Some(&summary), None), "rain");
+        summary.status = MatchStatus::Result {
+            description: "Worcestershire won by 18 runs".to_string(),
+            winner_team_id: Some("1509".to_string()),
+            margin: Some("18 runs".to_string()),
+        };
+        assert_eq!(class_of(Some(&summary), None), "result");
+    }
+
+    #[test]
+    fn ndjson_record_shape() {
+        let mut bar = StatusBar::new(StatusBarFormat::Ndjson);
+        assert_eq!(bar.header(), None);
+
+        let line = bar.line(Some(&example()), Some("timed out")).unwrap();
+        assert!(!line.contains('\n'));
+        let record: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(record["schema_version"], SCHEMA_VERSION);
+        assert!(record["fetched_at"].as_u64().unwrap() > 0);
+        assert_eq!(record["error"], "timed out");
+        let summary = &record["summary"];
+        assert_eq!(summary["status"], "Live");
+        assert_eq!(summary["match_summary"]["balls_per_over"], 6);
+        assert_eq!(summary["current_innings"]["runs"], 29);
+        assert!(summary.get("debug_string").is_none());
+
+        // and it reads back in
+        let record: SummaryRecord = serde_json::from_str(&line).unwrap();
+        assert_eq!(record.summary.unwrap().current_innings.wickets, 3);
+
+        let line = bar.line(None, None).unwrap();
+        let record: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert!(record["summary"].is_null());
+        assert!(record["error"].is_null());
+    }
+}
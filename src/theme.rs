@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+
+use crate::errors::Error;
+use crate::wicketick::{BallOutcome, Batter, CommentaryBall, SimpleSummary};
+
+pub static THEME_NAMES: [&str; 4] = ["classic", "dark", "light", "mono"];
+
+// Theme is the styles every phase draws with
+#[derive(Clone, Debug)]
+pub struct Theme {
+    // source and match selection
+    pub menu: Style,
+    // the minimal ticker, commentary and the dashboard
+    pub ticker: Style,
+    // the relaxed ticker's panels
+    pub panel: Style,
+    // the highlighted entry of a list
+    pub selected: Style,
+    pub striker: Style,
+    pub wicket: Style,
+    pub boundary: Style,
+    // failed and stale updates
+    pub error: Style,
+    // accents to pick teams out by, keyed by any of the team's names
+    pub team_colours: Vec<(String, Color)>,
+    // mono doesn't use colour at all, team colours included
+    colour: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl Theme {
+    // classic is white on green menus, a black ticker and blue panels
+    pub fn classic() -> Self {
+        Self {
+            menu: Style::default().fg(Color::White).bg(Color::Green),
+            ticker: Style::default().fg(Color::White).bg(Color::Black),
+            panel: Style::default().fg(Color::White).bg(Color::Blue),
+            selected: Style::default().fg(Color::Black).bg(Color::White),
+            striker: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            wicket: Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+            boundary: Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+            error: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            team_colours: vec![],
+            colour: true,
+        }
+    }
+
+    // dark keeps the terminal's own background
+    pub fn dark() -> Self {
+        Self {
+            menu: Style::default().fg(Color::White),
+            ticker: Style::default().fg(Color::White),
+            panel: Style::default().fg(Color::Gray),
+            selected: Style::default().fg(Color::Black).bg(Color::Cyan),
+            ..Self::classic()
+        }
+    }
+
+    // light is for terminals with a pale background
+    pub fn light() -> Self {
+        Self {
+            menu: Style::default().fg(Color::Black),
+            ticker: Style::default().fg(Color::Black),
+            panel: Style::default().fg(Color::Black),
+            selected: Style::default().fg(Color::White).bg(Color::Blue),
+            striker: Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::BOLD),
+            wicket: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            boundary: Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+            ..Self::classic()
+        }
+    }
+
+    // mono gets by on bold and reversed text alone
+    pub fn mono() -> Self {
+        Self {
+            menu: Style::default(),
+            ticker: Style::default(),
+            panel: Style::default(),
+            selected: Style::default().add_modifier(Modifier::REVERSED),
+            striker: Style::default().add_modifier(Modifier::BOLD),
+            wicket: Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            boundary: Style::default().add_modifier(Modifier::BOLD),
+            error: Style::default().add_modifier(Modifier::BOLD),
+            team_colours: vec![],
+            colour: false,
+        }
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "classic" => Some(Self::classic()),
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "mono" | "monochrome" => Some(Self::mono()),
+            _ => None,
+        }
+    }
+
+    // load takes one of THEME_NAMES, or a colour name or hex code to use as the classic
+    // theme's background, plus a colour per team. Setting NO_COLOR (see no-color.org)
+    // always gets mono.
+    pub fn load(
+        name: Option<&str>,
+        team_colours: &BTreeMap<String, String>,
+    ) -> Result<Self, Error> {
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Ok(Self::mono());
+        }
+        let mut theme = match name {
+            None => Self::default(),
+            Some(name) => match Self::named(name) {
+                Some(theme) => theme,
+                None => Self::classic().with_background(parse_colour(name).map_err(|_| {
+                    Error::Todo(format!(
+                        "unknown theme {}, try one of {} or a colour",
+                        name,
+                        THEME_NAMES.join(", ")
+                    ))
+                })?),
+            },
+        };
+        for (team, colour) in team_colours {
+            let colour = parse_colour(colour).map_err(|_| {
+                Error::Todo(format!(
+                    "unknown colour {} for {} in [team_colours]",
+                    colour, team
+                ))
+            })?;
+            theme.team_colours.push((team.clone(), colour));
+        }
+        Ok(theme)
+    }
+
+    pub fn with_background(mut self, background: Color) -> Self {
+        self.menu = self.menu.bg(background);
+        self.ticker = self.ticker.bg(background);
+        self.panel = self.panel.bg(background);
+        self
+    }
+
+    // team is the accent for one of the teams in the match, if it has one
+    pub fn team(&self, summary: &SimpleSummary, team_id: &str) -> Style {
+        let team = summary.match_summary.team(team_id);
+        let Some(team) = team.filter(|_| self.colour) else {
+            return Style::default();
+        };
+        self.team_colours
+            .iter()
+            .find(|(name, _)| team.goes_by(name))
+            .map(|(_, colour)| Style::default().fg(*colour))
+            .unwrap_or_default()
+    }
+
+    // batting is the accent for whoever is batting
+    pub fn batting(&self, summary: &SimpleSummary) -> Style {
+        self.team(summary, &summary.current_innings.batting_team_id)
+    }
+
+    pub fn bowling(&self, summary: &SimpleSummary) -> Style {
+        self.team(summary, &summary.current_innings.bowling_team_id)
+    }
+
+    pub fn batter(&self, batter: &Batter) -> Style {
+        match batter.on_strike() {
+            true => self.striker,
+            false => Style::default(),
+        }
+    }
+
+    pub fn ball(&self, ball: &BallOutcome) -> Style {
+        match ball {
+            BallOutcome::Wicket => self.wicket,
+            b if b.is_boundary() => self.boundary,
+            _ => Style::default(),
+        }
+    }
+
+    pub fn commentary(&self, ball: &CommentaryBall) -> Style {
+        if ball.is_wicket() {
+            return self.wicket;
+        }
+        if ball.is_boundary() {
+            return self.boundary;
+        }
+        Style::default()
+    }
+
+    // batters is the active batters with the striker picked out
+    pub fn batters(&self, summary: &SimpleSummary) -> Vec<Span<'static>> {
+        summary.active_players.batters_with(
+            |batter| Span::styled(batter.display(), self.batter(batter)),
+            Span::raw,
+        )
+    }
+
+    // recent_overs is SimpleSummary::display_recent_overs with wickets and boundaries
+    // picked out
+    pub fn recent_overs(&self, summary: &SimpleSummary, overs: usize) -> Vec<Span<'static>> {
+        summary.recent_overs_with(
+            overs,
+            |ball| Span::styled(ball.display(), self.ball(ball)),
+            Span::raw,
+        )
+    }
+}
+
+// parse_colour takes a name like blue or a hex code like #1e1e2e
+fn parse_colour(colour: &str) -> Result<Color, Error> {
+    Color::from_str(colour.trim()).map_err(|_| Error::Todo(format!("unknown colour {}", colour)))
+}
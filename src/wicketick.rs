@@ -1,16 +1,31 @@
+use std::collections::BTreeMap;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Sub};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use async_trait::async_trait;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use tokio::time;
 
 use crate::errors::Error;
-use crate::{cricinfo, errors};
+use crate::replay::ReplaySpeed;
+use crate::{cricinfo, errors, follow};
 
+pub mod events;
+
+// Source describes the built in places we know how to get match summaries from
 #[derive(Clone)]
 pub enum Source {
     Cricinfo { match_id: Option<String> },
     LocalCricinfo { filename: String },
-    _SomeApi { base_url: String, api_token: String },
+    // a session of cricinfo responses saved with --record
+    Replay { dir: String, speed: ReplaySpeed },
+    // whichever cricinfo match the team is playing in
+    FollowTeam { team: String },
 }
 
 impl fmt::Display for Source {
@@ -18,74 +33,418 @@ impl fmt::Display for Source {
         match self {
             Source::Cricinfo { match_id } => write!(f, "CricInfo(match_id:{:?})", match_id),
             Source::LocalCricinfo { filename } => write!(f, "CricInfo (local file {})", filename),
-            Source::_SomeApi {
-                base_url: _,
-                api_token: _,
-            } => write!(f, "_SomeApi"),
+            Source::Replay { dir, speed } => write!(f, "CricInfo (replay of {} {:?})", dir, speed),
+            Source::FollowTeam { team } => write!(f, "CricInfo (following {})", team),
+        }
+    }
+}
+
+impl Source {
+    // into_match_source builds the MatchSource implementation for a built in source,
+    // cricinfo lists matches from the first of `discovery` it can
+    pub fn into_match_source(self, discovery: &[String]) -> Arc<dyn MatchSource> {
+        match self {
+            Source::Cricinfo { match_id } => {
+                Arc::new(cricinfo::Cricinfo::new(match_id).discovering_from(discovery.to_vec()))
+            }
+            Source::LocalCricinfo { filename } => Arc::new(cricinfo::LocalCricinfo::new(filename)),
+            Source::Replay { dir, speed } => Arc::new(cricinfo::CricinfoReplay::new(dir, speed)),
+            Source::FollowTeam { team } => Arc::new(follow::FollowTeam::new(
+                Arc::new(cricinfo::Cricinfo::new(None).discovering_from(discovery.to_vec())),
+                team,
+            )),
         }
     }
 }
 
-// TODO really we need a trait for all the things we need to do with a
-// impl Source {
-//     fn should_poll(self) -> bool {
-//         match cricinfo {}
-//     }
-// }
+// MatchSource is everything the ticker needs from a feed of match data.
+// Implement this to plug a new feed into the ticker.
+#[async_trait]
+pub trait MatchSource: Send + Sync {
+    // fetch_summary gets the latest summary of the match the source is tracking
+    async fn fetch_summary(&self) -> Result<SimpleSummary, Error>;
+
+    // list_matches gets the matches the source can offer summaries of
+    async fn list_matches(&self) -> Result<Vec<MatchListing>, Error>;
+
+    // select_match returns a source tracking one of the matches from list_matches
+    fn select_match(&self, listing: &MatchListing) -> Arc<dyn MatchSource>;
+
+    // should_poll is false when fetching more than once would never show anything new
+    fn should_poll(&self) -> bool;
+
+    // cached_summary is whatever the source last saw and when, to show until the first
+    // fetch is done
+    fn cached_summary(&self) -> Option<(SimpleSummary, SystemTime)> {
+        None
+    }
+
+    // follows_on is true when the source moves on to another match once its match is
+    // over, so there's still a point polling after a result
+    fn follows_on(&self) -> bool {
+        false
+    }
+
+    // fixed_poll_interval is for sources that keep their own time rather than the
+    // match's, like replays. It's how often to poll them given the ticker's interval,
+    // whatever state the match is in.
+    fn fixed_poll_interval(&self, _interval: time::Duration) -> Option<time::Duration> {
+        None
+    }
+
+    fn name(&self) -> String;
+}
+
+// MatchListing is one entry in the list of matches a source can offer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MatchListing {
+    pub match_id: String,
+    pub state: ListingState,
+    // e.g. international, domestic
+    pub category: String,
+    pub team_one: ListedTeam,
+    pub team_two: ListedTeam,
+    pub start_time: String,
+    pub result: Option<String>,
+}
+
+impl MatchListing {
+    // involves is true when one of the teams is listed by the given name, ignoring case
+    pub fn involves(&self, team: &str) -> bool {
+        [&self.team_one, &self.team_two]
+            .iter()
+            .any(|t| t.goes_by(team))
+    }
+
+    // might_involve is true when one of the teams could be the given one. Listings
+    // often only have abbreviations, so Worcestershire might be WORCS.
+    pub fn might_involve(&self, team: &str) -> bool {
+        [&self.team_one, &self.team_two]
+            .iter()
+            .any(|t| t.might_be(team))
+    }
+
+    // WORCS 187/6 (17/17 ov) v BEARS 29/3 (4.2/17 ov)  18:30 local | 17:30 GMT
+    pub fn display(&self) -> String {
+        let status = match &self.result {
+            Some(result) => result.clone(),
+            None => self.start_time.clone(),
+        };
+        format!(
+            "{} v {}  {}",
+            self.team_one.display(),
+            self.team_two.display(),
+            status
+        )
+    }
+}
+
+// ListingState is where a listed match is up to, used to group the listing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ListingState {
+    Live,
+    Upcoming,
+    Recent,
+}
+
+impl fmt::Display for ListingState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListingState::Live => write!(f, "Live"),
+            ListingState::Upcoming => write!(f, "Upcoming"),
+            ListingState::Recent => write!(f, "Recent"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ListedTeam {
+    pub name: String,
+    // free text score, e.g. "121 & 136 (47 ov)", empty before they bat
+    pub score: String,
+}
+
+impl ListedTeam {
+    pub fn goes_by(&self, team: &str) -> bool {
+        self.name.eq_ignore_ascii_case(team.trim())
+    }
+
+    // might_be is true when the listed name is the team's, or one could be short for
+    // the other
+    pub fn might_be(&self, team: &str) -> bool {
+        self.goes_by(team) || abbreviates(&self.name, team) || abbreviates(team, &self.name)
+    }
+
+    pub fn display(&self) -> String {
+        if self.score.is_empty() {
+            return self.name.clone();
+        }
+        format!("{} {}", self.name, self.score)
+    }
+}
+
+// display_gmt_time shows the time of day, e.g. 14:05 GMT
+pub fn display_gmt_time(at: SystemTime) -> String {
+    let secs = at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() % 86400;
+    format!("{:02}:{:02} GMT", secs / 3600, secs % 3600 / 60)
+}
+
+// abbreviates is true when the letters of short all appear in order in name, starting
+// with its first, like WORCS in Worcestershire or BEARS in Birmingham Bears
+fn abbreviates(short: &str, name: &str) -> bool {
+    let letters = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<Vec<char>>()
+    };
+    let (short, name) = (letters(short), letters(name));
+    if short.is_empty() || short.len() >= name.len() || short[0] != name[0] {
+        return false;
+    }
+    let mut rest = name.iter();
+    short.iter().all(|c| rest.any(|n| n == c))
+}
 
 pub static DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
 pub static DEFAULT_POLL_INTERVAL: time::Duration =
     time::Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS);
 
+// how much longer than the usual interval to wait between polls when play has stopped
+static BREAK_POLL_FACTOR: u32 = 4;
+static STUMPS_POLL_INTERVAL: time::Duration = time::Duration::from_secs(15 * 60);
+// before the start there's nothing to see, but check back in time for the toss
+static PRE_MATCH_POLL_INTERVAL: time::Duration = time::Duration::from_secs(30 * 60);
+// long enough to take in the result before a source that follows on moves on
+static FOLLOW_ON_POLL_INTERVAL: time::Duration = time::Duration::from_secs(10 * 60);
+
+pub static DEFAULT_BACKOFF_MAX_SECS: u64 = 600;
+pub static DEFAULT_MAX_RETRIES: u32 = 20;
+
+// Backoff controls how long to wait between fetches once they start failing
+#[derive(Clone, Copy, Debug)]
+pub struct Backoff {
+    // the delay never grows past this
+    pub max: time::Duration,
+    // how much each delay is randomly stretched or shrunk by, as a fraction of it
+    pub jitter: f64,
+    // how many retries after a failed fetch can fail too before giving up, so one more
+    // failure than this in a row gives up, or never if none
+    pub max_retries: Option<u32>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            max: time::Duration::from_secs(DEFAULT_BACKOFF_MAX_SECS),
+            jitter: 0.2,
+            max_retries: Some(DEFAULT_MAX_RETRIES),
+        }
+    }
+}
+
+impl Backoff {
+    // delay is how long to wait after `failures` failed fetches in a row, doubling
+    // from the normal interval each time
+    pub fn delay(&self, interval: time::Duration, failures: u32) -> time::Duration {
+        let doublings = failures.saturating_sub(1).min(16);
+        // never poll more often than we would have if things were working
+        let backed_off = interval
+            .saturating_mul(1 << doublings)
+            .min(self.max.max(interval));
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return backed_off;
+        }
+        let factor = 1.0 + rand::thread_rng().gen_range(-jitter..=jitter);
+        backed_off.mul_f64(factor)
+    }
+
+    pub fn should_give_up(&self, failures: u32) -> bool {
+        match self.max_retries {
+            Some(max) => failures > max,
+            None => false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct WickeTick {
-    pub source: Source,
+    pub source: Arc<dyn MatchSource>,
     pub summary: Option<SimpleSummary>,
     pub last_refresh: Option<time::Instant>,
+    // when the summary was saved, if it came out of a cache rather than a fetch
+    pub cached_at: Option<SystemTime>,
     pub poll_interval: Option<time::Duration>,
+    pub backoff: Backoff,
+    // adaptive picks the poll interval from the state of the match, rather than
+    // always using poll_interval
+    pub adaptive: bool,
 }
 
 impl WickeTick {
-    pub fn new(source: Source, poll_interval: Option<time::Duration>) -> Self {
+    pub fn new(source: Arc<dyn MatchSource>, poll_interval: Option<time::Duration>) -> Self {
         let poll_t = match poll_interval {
             Some(t) => t,
             None => DEFAULT_POLL_INTERVAL,
         };
-        return Self {
+        Self {
             source,
             summary: None,
             last_refresh: None,
+            cached_at: None,
             poll_interval: Some(poll_t),
-        };
+            backoff: Backoff::default(),
+            adaptive: true,
+        }
+    }
+
+    // set_summary takes on a newly fetched summary. Sources may only have the latest
+    // wicket, so the ones we saw fall earlier in the innings are kept from before.
+    pub fn set_summary(&mut self, mut summary: SimpleSummary) {
+        if let Some(previous) = &self.summary {
+            let innings = &summary.current_innings;
+            let same_innings = previous.same_match(&summary)
+                && previous.current_innings.number == innings.number
+                && previous.current_innings.batting_team_id == innings.batting_team_id;
+            if same_innings {
+                for fow in &previous.fall_of_wickets {
+                    let known = summary
+                        .fall_of_wickets
+                        .iter()
+                        .any(|f| f.wicket == fow.wicket);
+                    // a wicket can be overturned on review
+                    if !known && fow.wicket <= innings.wickets {
+                        summary.fall_of_wickets.push(fow.clone());
+                    }
+                }
+                summary.fall_of_wickets.sort_by_key(|f| f.wicket);
+            }
+        }
+        self.summary = Some(summary);
+    }
+
+    // load_cached starts off with the source's cached summary, if it has one
+    pub fn load_cached(&mut self) {
+        if let Some((summary, saved_at)) = self.source.cached_summary() {
+            self.summary = Some(summary);
+            self.cached_at = Some(saved_at);
+        }
+    }
+
+    // cached_text marks a summary from the cache until a fetch has confirmed it,
+    // e.g. CACHED 14:05 GMT
+    pub fn cached_text(&self) -> Option<String> {
+        match (self.cached_at, self.last_refresh) {
+            (Some(at), None) => Some(format!("CACHED {}", display_gmt_time(at))),
+            _ => None,
+        }
     }
 
     pub async fn refetch(&self) -> Result<SimpleSummary, Error> {
-        match self.source.clone() {
-            Source::Cricinfo {
-                match_id: Some(m_id),
-            } => cricinfo::get_match_summary(m_id).await,
-            Source::Cricinfo { match_id: None } => {
-                // Nothing to refresh
-                Err(Error::Todo("no match id".to_string()))
+        self.source.fetch_summary().await
+    }
+
+    // poll_interval_for is how long to wait before polling again given the state
+    // of the match, or None if there's no point polling again
+    pub fn poll_interval_for(&self, status: &MatchStatus) -> Option<time::Duration> {
+        let interval = self.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL);
+        if status.is_finished() {
+            return self
+                .source
+                .follows_on()
+                .then(|| FOLLOW_ON_POLL_INTERVAL.max(interval));
+        }
+        if let Some(fixed) = self.source.fixed_poll_interval(interval) {
+            return Some(fixed);
+        }
+        if !self.adaptive {
+            return Some(interval);
+        }
+        match status {
+            MatchStatus::Live => Some(interval),
+            MatchStatus::InningsBreak | MatchStatus::Break(_) | MatchStatus::RainDelay => {
+                Some(interval * BREAK_POLL_FACTOR)
             }
-            Source::LocalCricinfo { filename } => cricinfo::load_match_summary(filename),
-            Source::_SomeApi {
-                base_url: _,
-                api_token: _,
-            } => Err(Error::Todo("not implemented".to_string())),
+            MatchStatus::Stumps => Some(STUMPS_POLL_INTERVAL.max(interval)),
+            MatchStatus::Scheduled { starts_in, .. } => {
+                let slow = PRE_MATCH_POLL_INTERVAL.max(interval);
+                match starts_in {
+                    // don't sleep through the first ball
+                    Some(starts_in) => Some((*starts_in).clamp(interval, slow)),
+                    None => Some(slow),
+                }
+            }
+            MatchStatus::Result { .. } => None,
         }
     }
 }
 
-// Simple summary just stores one innings, for now
-#[derive(Clone)]
+// Simple summary holds the live innings and players, plus the rest of the match
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SimpleSummary {
     pub current_innings: Innings,
+    pub match_summary: MatchSummary,
+    pub partnership: Option<Partnership>,
+    // wickets of the current innings, in the order they fell
+    pub fall_of_wickets: Vec<FallOfWicket>,
+    // the last few overs, oldest first, the last of which may be incomplete
+    pub recent_overs: Vec<RecentOver>,
+    pub status: MatchStatus,
+    // free text description of the state of play, e.g. "Bears require another 159 runs..."
+    pub status_text: String,
+    // the latest balls of commentary, the source only sends the last few overs
+    pub commentary: Vec<CommentaryBall>,
     pub active_players: ActivePlayers,
+    #[serde(skip)]
     pub debug_string: String,
 }
 
+// SCHEMA_VERSION goes up whenever the serialised model changes in a way that would
+// break something reading it. 2 added balls_per_over to the match summary, 3 made
+// innings overs an Overs rather than the feed's string.
+pub static SCHEMA_VERSION: u32 = 3;
+
+// SummaryRecord is what we write out for other tools to read, one per poll
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SummaryRecord {
+    pub schema_version: u32,
+    // seconds since the unix epoch
+    pub fetched_at: u64,
+    // the last good summary, if there's been one
+    pub summary: Option<SimpleSummary>,
+    // set when the summary is out of date because the last fetch failed
+    pub error: Option<String>,
+}
+
+impl SummaryRecord {
+    pub fn new(summary: Option<SimpleSummary>, error: Option<String>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            summary,
+            error,
+        }
+    }
+}
+
 impl SimpleSummary {
+    // same_match is false when the teams change, e.g. when following a team onto
+    // their next match
+    pub fn same_match(&self, other: &SimpleSummary) -> bool {
+        let team_ids = |s: &SimpleSummary| {
+            s.match_summary
+                .teams
+                .iter()
+                .map(|t| t.id.clone())
+                .collect::<Vec<_>>()
+        };
+        team_ids(self) == team_ids(other)
+    }
+
     // display will just return the simplest summary.
     // display should be called on each summary field by the configurations in order
     // to get the relevant strings
@@ -93,7 +452,68 @@ impl SimpleSummary {
         // if self.debug_string != "" {
         //     return format!("{} {}", self.debug_string, self.current_innings.display());
         // }
-        self.current_innings.display()
+        if self.match_summary.innings.is_empty() {
+            // there's no score to show before the first ball
+            if self.current_innings.number == 0 {
+                return self.display_status();
+            }
+            return self.current_innings.display();
+        }
+        self.match_summary.display()
+    }
+
+    // display_status prefers the source's description of play, e.g. the chase equation,
+    // falling back to the status when there isn't one
+    pub fn display_status(&self) -> String {
+        match (&self.status, self.status_text.is_empty()) {
+            (MatchStatus::Live, false) => self.status_text.clone(),
+            (status, _) => status.display(),
+        }
+    }
+
+    // display_recent_overs shows the balls of the last `overs` overs
+    // • W 2 1 1 1 | 4 • 4
+    pub fn display_recent_overs(&self, overs: usize) -> String {
+        self.recent_overs_with(overs, |ball| ball.display(), |gap| gap.to_string())
+            .concat()
+    }
+
+    // recent_overs_with lays out the last `overs` overs like display_recent_overs,
+    // turning each ball and each gap into a T, e.g. for the tickers to style
+    pub fn recent_overs_with<T>(
+        &self,
+        overs: usize,
+        ball: impl Fn(&BallOutcome) -> T,
+        gap: impl Fn(&'static str) -> T,
+    ) -> Vec<T> {
+        let skip = self.recent_overs.len().saturating_sub(overs);
+        let mut parts = vec![];
+        for (i, over) in self.recent_overs.iter().skip(skip).enumerate() {
+            if i > 0 {
+                parts.push(gap(" | "));
+            }
+            for (j, b) in over.balls.iter().enumerate() {
+                if j > 0 {
+                    parts.push(gap(" "));
+                }
+                parts.push(ball(b));
+            }
+        }
+        parts
+    }
+
+    // FoW: 1-4 (Smith, 0.5 ov), 2-18 (Haynes 14, 2.5 ov)
+    pub fn display_fall_of_wickets(&self) -> String {
+        if self.fall_of_wickets.is_empty() {
+            return "FoW: none".to_string();
+        }
+        let wickets = self
+            .fall_of_wickets
+            .iter()
+            .map(|f| f.display())
+            .collect::<Vec<String>>()
+            .join(", ");
+        format!("FoW: {}", wickets)
     }
 
     pub fn new() -> Self {
@@ -105,18 +525,177 @@ impl Default for SimpleSummary {
     fn default() -> Self {
         Self {
             current_innings: Innings::new(),
+            match_summary: MatchSummary::default(),
+            partnership: None,
+            fall_of_wickets: vec![],
+            recent_overs: vec![],
+            status: MatchStatus::Live,
+            status_text: "".to_string(),
+            commentary: vec![],
             active_players: ActivePlayers::default(),
             debug_string: "".to_string(),
         }
     }
 }
 
-#[derive(Clone)]
+// MatchStatus is where the match is up to
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MatchStatus {
+    Scheduled {
+        start: String,
+        // how long until the start, going by the source's clock, if it said
+        starts_in: Option<time::Duration>,
+    },
+    Live,
+    InningsBreak,
+    Stumps,
+    // lunch, tea, drinks...
+    Break(String),
+    // covers bad light too, anything with the weather holding up play
+    RainDelay,
+    Result {
+        // e.g. Sussex won by 88 runs
+        description: String,
+        winner_team_id: Option<String>,
+        // e.g. 88 runs, none for a draw or no result
+        margin: Option<String>,
+    },
+}
+
+impl MatchStatus {
+    pub fn display(&self) -> String {
+        match self {
+            MatchStatus::Scheduled { start, .. } => format!("Starts {}", start),
+            MatchStatus::Live => "Live".to_string(),
+            MatchStatus::InningsBreak => "Innings break".to_string(),
+            MatchStatus::Stumps => "Stumps".to_string(),
+            MatchStatus::Break(name) => name.clone(),
+            MatchStatus::RainDelay => "Rain delay".to_string(),
+            MatchStatus::Result { description, .. } => description.clone(),
+        }
+    }
+
+    // is_finished is true once there's a result, nothing will change after that
+    pub fn is_finished(&self) -> bool {
+        matches!(self, MatchStatus::Result { .. })
+    }
+}
+
+// MatchSummary holds every innings of the match so far, in the order they were batted
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MatchSummary {
+    pub teams: Vec<Team>,
+    pub innings: Vec<Innings>,
+    #[serde(default = "default_balls_per_over")]
+    pub balls_per_over: u32,
+}
+
+impl Default for MatchSummary {
+    fn default() -> Self {
+        Self {
+            teams: vec![],
+            innings: vec![],
+            balls_per_over: DEFAULT_BALLS_PER_OVER,
+        }
+    }
+}
+
+impl MatchSummary {
+    pub fn team(&self, team_id: &str) -> Option<&Team> {
+        self.teams.iter().find(|t| t.id == team_id)
+    }
+
+    fn abbreviation(&self, team_id: &str) -> String {
+        match self.team(team_id) {
+            Some(team) => team.abbreviation.clone(),
+            None => team_id.to_string(),
+        }
+    }
+
+    // WORCS 187/6 (17) — BEARS 29/3 (4.2) need 159
+    // ENG 371 — WI 121 & 136/5 (47) trail by 114
+    pub fn display(&self) -> String {
+        let Some(last) = self.innings.last() else {
+            return "".to_string();
+        };
+
+        // group each team's innings together, in the order the teams first batted
+        let mut batting_order: Vec<&str> = vec![];
+        for innings in &self.innings {
+            if !batting_order.contains(&innings.batting_team_id.as_str()) {
+                batting_order.push(&innings.batting_team_id);
+            }
+        }
+        // once teams bat twice the overs of finished innings are just noise
+        let show_all_overs = batting_order.len() == self.innings.len();
+
+        let scores = batting_order
+            .iter()
+            .map(|team_id| {
+                let innings_scores = self
+                    .innings
+                    .iter()
+                    .filter(|i| i.batting_team_id == *team_id)
+                    .map(|i| {
+                        if show_all_overs || std::ptr::eq(i, last) {
+                            format!("{} ({})", i.display_score(), i.overs.display())
+                        } else {
+                            i.display_score()
+                        }
+                    })
+                    .collect::<Vec<String>>()
+                    .join(" & ");
+                format!("{} {}", self.abbreviation(team_id), innings_scores)
+            })
+            .collect::<Vec<String>>()
+            .join(" \u{2014} ");
+
+        let situation = match last.target {
+            Some(target) if target > last.runs => format!(" need {}", target - last.runs),
+            Some(_) => "".to_string(),
+            None if self.innings.len() > 1 && last.lead > 0 => {
+                format!(" lead by {}", last.lead)
+            }
+            None if self.innings.len() > 1 && last.lead < 0 => {
+                format!(" trail by {}", -last.lead)
+            }
+            None => "".to_string(),
+        };
+
+        format!("{}{}", scores, situation)
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Team {
+    pub id: String,
+    pub name: String,
+    pub short_name: String,
+    pub abbreviation: String,
+}
+
+impl Team {
+    // goes_by is true when the team is known by the given id or name, ignoring case
+    pub fn goes_by(&self, name: &str) -> bool {
+        [&self.id, &self.name, &self.short_name, &self.abbreviation]
+            .iter()
+            .any(|n| n.eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Innings {
+    // 1 for the first innings of the match, 2 for the second...
+    pub number: u32,
+    pub batting_team_id: String,
+    pub bowling_team_id: String,
     pub runs: u32,
     pub wickets: u32,
-    pub overs: String,
+    pub overs: Overs,
     pub target: Option<u32>,
+    // runs the batting team are ahead by, negative when behind
+    pub lead: i32,
+    pub extras: Extras,
 }
 
 impl Innings {
@@ -124,13 +703,20 @@ impl Innings {
     pub fn display(&self) -> String {
         let runs = self.runs;
         let wickets = self.wickets;
-        let overs = &self.overs;
+        let overs = self.overs.display();
         let target = match self.target {
             None => "".to_string(),
             Some(t) => format!(" Target {}", t),
         };
-        let text = format!("{}-{} {}{}", runs, wickets, overs, target);
-        return text;
+        format!("{}-{} {}{}", runs, wickets, overs, target)
+    }
+
+    // 187/6, or just 115 when all out
+    pub fn display_score(&self) -> String {
+        if self.wickets >= 10 {
+            return format!("{}", self.runs);
+        }
+        format!("{}/{}", self.runs, self.wickets)
     }
 
     pub fn new() -> Self {
@@ -141,15 +727,268 @@ impl Innings {
 impl Default for Innings {
     fn default() -> Self {
         Self {
+            number: 0,
+            batting_team_id: "".to_string(),
+            bowling_team_id: "".to_string(),
             runs: 0,
             wickets: 0,
-            overs: "0".to_string(),
+            overs: Overs::default(),
             target: None,
+            lead: 0,
+            extras: Extras::default(),
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Extras {
+    pub byes: u32,
+    pub leg_byes: u32,
+    pub wides: u32,
+    pub no_balls: u32,
+    pub penalties: u32,
+}
+
+impl Extras {
+    pub fn total(&self) -> u32 {
+        self.byes + self.leg_byes + self.wides + self.no_balls + self.penalties
+    }
+
+    // 9 (b 0, lb 1, w 4, nb 4, pen 0)
+    pub fn display(&self) -> String {
+        format!(
+            "{} (b {}, lb {}, w {}, nb {}, pen {})",
+            self.total(),
+            self.byes,
+            self.leg_byes,
+            self.wides,
+            self.no_balls,
+            self.penalties
+        )
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecentOver {
+    // the over number counting from 1, so the 3rd over is bowled as 2.1 to 2.6
+    pub number: u32,
+    pub balls: Vec<BallOutcome>,
+}
+
+impl RecentOver {
+    pub fn display(&self) -> String {
+        self.balls
+            .iter()
+            .map(|b| b.display())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    pub fn runs(&self) -> u32 {
+        self.balls.iter().map(|b| b.runs()).sum()
+    }
+}
+
+// BallOutcome is what happened off a single delivery
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BallOutcome {
+    Dot,
+    // runs off the bat, including boundaries
+    Runs(u32),
+    Wicket,
+    // extras hold the total runs conceded from the delivery
+    Wide(u32),
+    NoBall(u32),
+    Bye(u32),
+    LegBye(u32),
+    // anything we don't recognise, kept as the source sent it
+    Other(String),
+}
+
+impl BallOutcome {
+    pub fn display(&self) -> String {
+        match self {
+            BallOutcome::Dot => "\u{2022}".to_string(),
+            BallOutcome::Runs(runs) => runs.to_string(),
+            BallOutcome::Wicket => "W".to_string(),
+            BallOutcome::Wide(1) => "wd".to_string(),
+            BallOutcome::Wide(runs) => format!("{}wd", runs),
+            BallOutcome::NoBall(1) => "nb".to_string(),
+            BallOutcome::NoBall(runs) => format!("{}nb", runs),
+            BallOutcome::Bye(runs) => format!("{}b", runs),
+            BallOutcome::LegBye(runs) => format!("{}lb", runs),
+            BallOutcome::Other(token) => token.clone(),
+        }
+    }
+
+    pub fn runs(&self) -> u32 {
+        match self {
+            BallOutcome::Dot | BallOutcome::Wicket | BallOutcome::Other(_) => 0,
+            BallOutcome::Runs(runs)
+            | BallOutcome::Wide(runs)
+            | BallOutcome::NoBall(runs)
+            | BallOutcome::Bye(runs)
+            | BallOutcome::LegBye(runs) => *runs,
+        }
+    }
+
+    pub fn is_boundary(&self) -> bool {
+        matches!(self, BallOutcome::Runs(4) | BallOutcome::Runs(6))
+    }
+
+    // wides and no balls have to be bowled again
+    pub fn is_legal(&self) -> bool {
+        !matches!(self, BallOutcome::Wide(_) | BallOutcome::NoBall(_))
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CommentaryBall {
+    pub innings: u32,
+    // 4.2, as a scorer would write it
+    pub overs_actual: String,
+    // 4.02, unique to the delivery within the innings so extras don't collide
+    pub overs_unique: String,
+    // Taylor to Ali
+    pub players: String,
+    // FOUR, 1 run, OUT...
+    pub event: String,
+    pub dismissal: String,
+    pub text: String,
+}
+
+impl CommentaryBall {
+    // key orders balls through the match, and identifies the same ball across polls
+    pub fn key(&self) -> (u32, u64) {
+        let unique = self.overs_unique.trim().parse::<f64>().unwrap_or(0.0);
+        (self.innings, (unique * 1000.0).round() as u64)
+    }
+
+    pub fn is_wicket(&self) -> bool {
+        !self.dismissal.is_empty() || self.event.trim().eq_ignore_ascii_case("out")
+    }
+
+    pub fn is_boundary(&self) -> bool {
+        ["four", "six"]
+            .iter()
+            .any(|e| self.event.trim().eq_ignore_ascii_case(e))
+    }
+
+    // 4.2 Taylor to Ali, 1 run
+    pub fn display(&self) -> String {
+        let mut text = format!("{} {}, {}", self.overs_actual, self.players, self.event);
+        if !self.dismissal.is_empty() {
+            text.push_str(&format!(" - {}", self.dismissal));
+        }
+        if !self.text.is_empty() {
+            text.push_str(&format!(". {}", self.text));
+        }
+        text
+    }
+}
+
+// Commentary gathers the commentary of many polls, without repeating balls
+#[derive(Clone, Default)]
+pub struct Commentary {
+    balls: BTreeMap<(u32, u64), CommentaryBall>,
+}
+
+impl Commentary {
+    // merge adds the balls we've not seen before, later copies of a ball replace
+    // earlier ones as the commentators fill in the text after the fact
+    pub fn merge(&mut self, balls: &[CommentaryBall]) {
+        for ball in balls {
+            self.balls.insert(ball.key(), ball.clone());
+        }
+    }
+
+    pub fn newest_first(&self) -> impl Iterator<Item = &CommentaryBall> {
+        self.balls.values().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.balls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.balls.is_empty()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Partnership {
+    // the wicket the partnership is for, e.g. 4 for the 4th wicket partnership
+    pub wicket: u32,
+    pub runs: u32,
+    pub overs: Overs,
+    pub run_rate: f64,
+}
+
+impl Partnership {
+    // P'ship 11 (9b) @7.33
+    pub fn display(&self) -> String {
+        format!(
+            "P'ship {} ({}b) @{:.2}",
+            self.runs,
+            self.overs.total_balls(),
+            self.run_rate
+        )
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FallOfWicket {
+    // 1 for the first wicket to fall...
+    pub wicket: u32,
+    // the team's score when the wicket fell
+    pub runs: u32,
+    pub overs: Overs,
+    pub batter: String,
+    pub batter_runs: Option<u32>,
+    pub batter_balls: Option<u32>,
+    // e.g. "b Waite"
+    pub dismissal: String,
+    // the partnership that the wicket ended
+    pub partnership: Partnership,
+}
+
+impl FallOfWicket {
+    // 3-18 (Haynes 14, 2.5 ov)
+    pub fn display(&self) -> String {
+        let batter = match self.batter_runs {
+            Some(runs) => format!("{} {}", self.batter, runs),
+            None => self.batter.clone(),
+        };
+        format!(
+            "{}-{} ({}, {} ov)",
+            self.wicket,
+            self.runs,
+            batter,
+            self.overs.display()
+        )
+    }
+
+    // 3-18 Haynes 14 (8) b Waite, 2.5 ov, P'ship 15
+    pub fn display_detailed(&self) -> String {
+        let figures = match (self.batter_runs, self.batter_balls) {
+            (Some(runs), Some(balls)) => format!(" {} ({})", runs, balls),
+            (Some(runs), None) => format!(" {}", runs),
+            _ => "".to_string(),
+        };
+        format!(
+            "{}-{} {}{} {}, {} ov, P'ship {}",
+            self.wicket,
+            self.runs,
+            self.batter,
+            figures,
+            self.dismissal,
+            self.overs.display(),
+            self.partnership.runs
+        )
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct ActivePlayers {
     pub batter_one: Option<Batter>,
     pub batter_two: Option<Batter>,
@@ -157,18 +996,21 @@ pub struct ActivePlayers {
     pub bowler_two: Option<Bowler>,
 }
 
-impl Default for ActivePlayers {
-    fn default() -> Self {
-        Self {
-            batter_one: None,
-            batter_two: None,
-            bowler_one: None,
-            bowler_two: None,
-        }
+impl ActivePlayers {
+    pub fn batters(&self) -> Vec<&Batter> {
+        [&self.batter_one, &self.batter_two]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    pub fn bowlers(&self) -> Vec<&Bowler> {
+        [&self.bowler_one, &self.bowler_two]
+            .into_iter()
+            .flatten()
+            .collect()
     }
-}
 
-impl ActivePlayers {
     // TODO change return type to e.g. be a tuple of the different things so they can be separated?
     pub fn display_bowlers(&self) -> String {
         let one_string = match &self.bowler_one {
@@ -183,32 +1025,53 @@ impl ActivePlayers {
     }
 
     pub fn display_batters(&self) -> String {
-        let one_string = match &self.batter_one {
-            Some(batter) => batter.display(),
-            None => "".to_string(),
-        };
-        let two_string = match &self.batter_two {
-            Some(batter) => batter.display(),
-            None => "".to_string(),
-        };
-        format!("{}     {}", one_string, two_string)
+        self.batters_with(|batter| batter.display(), |gap| gap.to_string())
+            .concat()
+    }
+
+    // batters_with lays out the batters like display_batters, turning each batter and
+    // the gap between them into a T
+    pub fn batters_with<T>(
+        &self,
+        batter: impl Fn(&Batter) -> T,
+        gap: impl Fn(&'static str) -> T,
+    ) -> Vec<T> {
+        let mut parts = vec![];
+        for b in self.batters() {
+            if !parts.is_empty() {
+                parts.push(gap("     "));
+            }
+            parts.push(batter(b));
+        }
+        parts
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Batter {
     name: String,
     runs: u32,
     balls_faced: u32,
+    fours: u32,
+    sixes: u32,
     on_strike: bool,
 }
 
 impl Batter {
-    pub fn new(name: &str, runs: u32, balls_faced: u32, on_strike: bool) -> Self {
+    pub fn new(
+        name: &str,
+        runs: u32,
+        balls_faced: u32,
+        fours: u32,
+        sixes: u32,
+        on_strike: bool,
+    ) -> Self {
         Self {
             name: name.to_string(),
             runs,
             balls_faced,
+            fours,
+            sixes,
             on_strike,
         }
     }
@@ -224,24 +1087,58 @@ impl Batter {
             self.name, strike_marker, self.runs, self.balls_faced
         )
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn runs(&self) -> u32 {
+        self.runs
+    }
+
+    pub fn balls_faced(&self) -> u32 {
+        self.balls_faced
+    }
+
+    pub fn fours(&self) -> u32 {
+        self.fours
+    }
+
+    pub fn sixes(&self) -> u32 {
+        self.sixes
+    }
+
+    pub fn on_strike(&self) -> bool {
+        self.on_strike
+    }
+
+    // runs per hundred balls
+    pub fn strike_rate(&self) -> f64 {
+        if self.balls_faced == 0 {
+            return 0.0;
+        }
+        self.runs as f64 * 100.0 / self.balls_faced as f64
+    }
 }
 
 // TODO separate the figures part of a batter and bowler from the batter and bowler struct types?
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Bowler {
     name: String,
     overs: Overs,
     wickets: u32,
     runs_conceded: u32,
+    maidens: u32,
 }
 
 impl Bowler {
-    pub fn new(name: &str, overs: Overs, wickets: u32, runs_conceded: u32) -> Self {
+    pub fn new(name: &str, overs: Overs, wickets: u32, runs_conceded: u32, maidens: u32) -> Self {
         Self {
             name: name.to_string(),
             overs,
             wickets,
             runs_conceded,
+            maidens,
         }
     }
 
@@ -255,70 +1152,454 @@ impl Bowler {
             self.overs.display()
         )
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn overs(&self) -> &Overs {
+        &self.overs
+    }
+
+    pub fn wickets(&self) -> u32 {
+        self.wickets
+    }
+
+    pub fn runs_conceded(&self) -> u32 {
+        self.runs_conceded
+    }
+
+    pub fn maidens(&self) -> u32 {
+        self.maidens
+    }
+
+    // runs conceded per over
+    pub fn economy(&self) -> f64 {
+        self.overs.run_rate(self.runs_conceded)
+    }
 }
 
-#[derive(Clone)]
+pub static DEFAULT_BALLS_PER_OVER: u32 = 6;
+
+pub fn default_balls_per_over() -> u32 {
+    DEFAULT_BALLS_PER_OVER
+}
+
+// Overs counts balls bowled as full overs and the balls of the over in progress.
+// Overs with different balls per over shouldn't be mixed, they compare by balls bowled.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Overs {
     full_overs: u32,
     spare_balls: u32,
+    // 6 nearly everywhere, but the feed says so for each match
+    #[serde(default = "default_balls_per_over")]
+    balls_per_over: u32,
+}
+
+impl Default for Overs {
+    fn default() -> Self {
+        Self::new(0, 0, DEFAULT_BALLS_PER_OVER)
+    }
 }
 
 impl Overs {
+    // new rolls spare balls past the end of the over over into full overs
+    pub fn new(full_overs: u32, spare_balls: u32, balls_per_over: u32) -> Self {
+        Self::from_balls(full_overs * balls_per_over + spare_balls, balls_per_over)
+    }
+
+    pub fn from_balls(balls: u32, balls_per_over: u32) -> Self {
+        let balls_per_over = balls_per_over.max(1);
+        Self {
+            full_overs: balls / balls_per_over,
+            spare_balls: balls % balls_per_over,
+            balls_per_over,
+        }
+    }
+
     pub fn display(&self) -> String {
         if self.spare_balls == 0 {
             return format!("{}", self.full_overs);
         }
-        return format!("{}.{}", self.full_overs, self.spare_balls);
+        format!("{}.{}", self.full_overs, self.spare_balls)
     }
 
-    pub fn from_str_with_default(s: &str) -> Self {
-        match Self::from_str(s) {
-            Ok(overs) => overs,
-            Err(e) => {
-                eprintln!("error from str with defaults {}", e);
-                Self::default()
-            }
+    pub fn full_overs(&self) -> u32 {
+        self.full_overs
+    }
+
+    pub fn spare_balls(&self) -> u32 {
+        self.spare_balls
+    }
+
+    pub fn balls_per_over(&self) -> u32 {
+        self.balls_per_over
+    }
+
+    // total_balls counts the legal balls bowled
+    pub fn total_balls(&self) -> u32 {
+        self.full_overs * self.balls_per_over + self.spare_balls
+    }
+
+    // as_f64 is the overs as a number for working out rates, so 4.3 is 4.5 overs
+    pub fn as_f64(&self) -> f64 {
+        self.total_balls() as f64 / self.balls_per_over as f64
+    }
+
+    // run_rate is runs per over for `runs` scored in these overs
+    pub fn run_rate(&self, runs: u32) -> f64 {
+        match self.total_balls() {
+            0 => 0.0,
+            _ => runs as f64 / self.as_f64(),
         }
     }
+
+    // remaining is what's left of an innings of `over_limit` overs
+    pub fn remaining(&self, over_limit: u32) -> Overs {
+        let limit = Overs::from_balls(over_limit * self.balls_per_over, self.balls_per_over);
+        limit - *self
+    }
+
+    // from_str_with_default is no overs for anything that doesn't parse, a wrong count
+    // is better than a poll that fails over one field
+    pub fn from_str_with_default(s: &str, balls_per_over: u32) -> Self {
+        Self::parse(s, balls_per_over).unwrap_or(Self::new(0, 0, balls_per_over))
+    }
+
+    // parse reads overs like "4.3", rejecting more spare balls than fit in an over
+    pub fn parse(s: &str, balls_per_over: u32) -> Result<Self, errors::Error> {
+        let parse_u32 = |n: &str| {
+            n.parse::<u32>()
+                .map_err(|e| errors::Error::ParseError(e.to_string()))
+        };
+        let (overs, balls) = match s.split_once(".") {
+            None => (parse_u32(s)?, 0),
+            Some((o, b)) => (parse_u32(o)?, parse_u32(b)?),
+        };
+        let balls_per_over = balls_per_over.max(1);
+        if balls >= balls_per_over {
+            return Err(errors::Error::ParseError(format!(
+                "{} has more than {} balls in an over",
+                s, balls_per_over
+            )));
+        }
+        Ok(Self {
+            full_overs: overs,
+            spare_balls: balls,
+            balls_per_over,
+        })
+    }
 }
 
 impl FromStr for Overs {
     type Err = errors::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.split_once(".") {
-            None => {
-                let o = (s
-                    .parse::<u32>()
-                    .map_err(|e| errors::Error::ParseError(e.to_string())))?;
-                Ok(Self {
-                    full_overs: o,
-                    spare_balls: 0,
-                })
-            }
-            Some((o, b)) => {
-                let overs = (o
-                    .parse::<u32>()
-                    .map_err(|e| errors::Error::ParseError(e.to_string())))?;
-                let balls = (b
-                    .parse::<u32>()
-                    .map_err(|e| errors::Error::ParseError(e.to_string())))?;
-                Ok(Self {
-                    full_overs: overs,
-                    spare_balls: balls,
-                })
-            }
-        }
+        Self::parse(s, DEFAULT_BALLS_PER_OVER)
     }
 }
 
-impl Default for Overs {
-    fn default() -> Self {
-        Self {
-            full_overs: 0,
-            spare_balls: 0,
-        }
+impl Add for Overs {
+    type Output = Overs;
+
+    fn add(self, other: Overs) -> Overs {
+        Overs::from_balls(
+            self.total_balls() + other.total_balls(),
+            self.balls_per_over,
+        )
     }
 }
 
-mod test {}
+// there's no such thing as negative overs, so this stops at zero
+impl Sub for Overs {
+    type Output = Overs;
+
+    fn sub(self, other: Overs) -> Overs {
+        Overs::from_balls(
+            self.total_balls().saturating_sub(other.total_balls()),
+            self.balls_per_over,
+        )
+    }
+}
+
+impl PartialEq for Overs {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_balls() == other.total_balls()
+    }
+}
+
+impl Eq for Overs {}
+
+impl Hash for Overs {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_balls().hash(state);
+    }
+}
+
+impl PartialOrd for Overs {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Overs {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.total_balls().cmp(&other.total_balls())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_rejects_too_many_balls() {
+        assert!(Overs::parse("4.7", 6).is_err());
+        assert!(Overs::parse("4.6", 6).is_err());
+        assert!(Overs::parse("4.5", 6).is_ok());
+        assert!(Overs::parse("4.5", 5).is_err());
+    }
+
+    #[test]
+    fn parse_counts_balls() {
+        let overs = Overs::parse("4.3", 6).unwrap();
+        assert_eq!(overs.total_balls(), 27);
+        assert_eq!(overs.display(), "4.3");
+        assert_eq!(Overs::parse("12", 6).unwrap().total_balls(), 72);
+    }
+
+    #[test]
+    fn from_balls_with_five_ball_overs() {
+        let overs = Overs::from_balls(23, 5);
+        assert_eq!(overs.full_overs(), 4);
+        assert_eq!(overs.spare_balls(), 3);
+        assert_eq!(overs.display(), "4.3");
+        assert_eq!(overs.as_f64(), 4.6);
+        assert_eq!(Overs::new(3, 8, 5).display(), "4.3");
+    }
+
+    #[test]
+    fn sub_stops_at_zero() {
+        let less = Overs::parse("2.1", 6).unwrap();
+        let more = Overs::parse("3.4", 6).unwrap();
+        assert_eq!((more - less).display(), "1.3");
+        assert_eq!((less - more).total_balls(), 0);
+    }
+
+    #[test]
+    fn remaining_of_twenty() {
+        let overs = Overs::parse("14.2", 6).unwrap();
+        assert_eq!(overs.remaining(20).display(), "5.4");
+        let overs = Overs::parse("14.2", 5).unwrap();
+        assert_eq!(overs.remaining(20).display(), "5.3");
+        assert_eq!(
+            Overs::parse("20", 6).unwrap().remaining(20).total_balls(),
+            0
+        );
+    }
+
+    #[test]
+    fn ordering_goes_by_balls_bowled() {
+        // 4.3 of five ball overs is 23 balls, 3.4 of six is 22
+        let fives = Overs::parse("4.3", 5).unwrap();
+        let sixes = Overs::parse("3.4", 6).unwrap();
+        assert!(fives > sixes);
+        assert!(Overs::parse("3.5", 6).unwrap() == fives);
+        assert_eq!(
+            fives.cmp(&Overs::parse("3.5", 6).unwrap()),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn listed_abbreviations_might_be_the_team() {
+        let listed = |name: &str| ListedTeam {
+            name: name.to_string(),
+            score: "".to_string(),
+        };
+        assert!(listed("WORCS").might_be("Worcestershire"));
+        assert!(listed("BEARS").might_be("Birmingham Bears"));
+        assert!(listed("Worcestershire").might_be("worcs"));
+        assert!(listed("worcs").goes_by("WORCS"));
+        assert!(!listed("WORCS").goes_by("Worcestershire"));
+        assert!(!listed("NOTTS").might_be("Worcestershire"));
+        assert!(!listed("ENG").might_be("Durham"));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_max() {
+        let backoff = Backoff {
+            max: time::Duration::from_secs(100),
+            jitter: 0.0,
+            max_retries: None,
+        };
+        let interval = time::Duration::from_secs(10);
+        let delays: Vec<u64> = (1..=6)
+            .map(|failures| backoff.delay(interval, failures).as_secs())
+            .collect();
+        assert_eq!(delays, vec![10, 20, 40, 80, 100, 100]);
+
+        // a max below the interval never polls faster than usual
+        let short = Backoff {
+            max: time::Duration::from_secs(1),
+            ..backoff
+        };
+        assert_eq!(short.delay(interval, 5), interval);
+    }
+
+    #[test]
+    fn backoff_jitter_stays_in_bounds() {
+        let backoff = Backoff {
+            max: time::Duration::from_secs(600),
+            jitter: 0.2,
+            max_retries: None,
+        };
+        let interval = time::Duration::from_secs(10);
+        for _ in 0..200 {
+            let delay = backoff.delay(interval, 2).as_secs_f64();
+            assert!((16.0..=24.0).contains(&delay), "{}", delay);
+        }
+    }
+
+    #[test]
+    fn gives_up_once_the_retries_have_failed_too() {
+        let backoff = Backoff {
+            max_retries: Some(3),
+            ..Backoff::default()
+        };
+        // the first failure and three retries makes four
+        assert!(!backoff.should_give_up(3));
+        assert!(backoff.should_give_up(4));
+        let never = Backoff {
+            max_retries: None,
+            ..Backoff::default()
+        };
+        assert!(!never.should_give_up(u32::MAX));
+    }
+
+    // Still is a source that's always the same, for testing how often it gets polled
+    struct Still {
+        follows_on: bool,
+        fixed: Option<time::Duration>,
+    }
+
+    #[async_trait]
+    impl MatchSource for Still {
+        async fn fetch_summary(&self) -> Result<SimpleSummary, Error> {
+            Ok(SimpleSummary::default())
+        }
+
+        async fn list_matches(&self) -> Result<Vec<MatchListing>, Error> {
+            Ok(vec![])
+        }
+
+        fn select_match(&self, _listing: &MatchListing) -> Arc<dyn MatchSource> {
+            Arc::new(Still { ..*self })
+        }
+
+        fn should_poll(&self) -> bool {
+            true
+        }
+
+        fn follows_on(&self) -> bool {
+            self.follows_on
+        }
+
+        fn fixed_poll_interval(&self, _interval: time::Duration) -> Option<time::Duration> {
+            self.fixed
+        }
+
+        fn name(&self) -> String {
+            "still".to_string()
+        }
+    }
+
+    fn still(follows_on: bool, fixed: Option<time::Duration>) -> WickeTick {
+        let source = Arc::new(Still { follows_on, fixed });
+        WickeTick::new(source, Some(time::Duration::from_secs(30)))
+    }
+
+    #[test]
+    fn poll_interval_follows_the_match() {
+        let secs =
+            |w: &WickeTick, status: MatchStatus| w.poll_interval_for(&status).map(|d| d.as_secs());
+        let scheduled = |starts_in: Option<u64>| MatchStatus::Scheduled {
+            start: "".to_string(),
+            starts_in: starts_in.map(time::Duration::from_secs),
+        };
+        let result = MatchStatus::Result {
+            description: "".to_string(),
+            winner_team_id: None,
+            margin: None,
+        };
+
+        let w = still(false, None);
+        assert_eq!(secs(&w, MatchStatus::Live), Some(30));
+        assert_eq!(secs(&w, MatchStatus::Break("Tea".to_string())), Some(120));
+        assert_eq!(secs(&w, MatchStatus::RainDelay), Some(120));
+        assert_eq!(secs(&w, MatchStatus::Stumps), Some(15 * 60));
+        assert_eq!(secs(&w, scheduled(None)), Some(30 * 60));
+        // wake up in time for the start, but no more often than usual
+        assert_eq!(secs(&w, scheduled(Some(10 * 60))), Some(10 * 60));
+        assert_eq!(secs(&w, scheduled(Some(5))), Some(30));
+        assert_eq!(secs(&w, result.clone()), None);
+
+        let fixed = WickeTick {
+            adaptive: false,
+            ..still(false, None)
+        };
+        assert_eq!(secs(&fixed, MatchStatus::Stumps), Some(30));
+        assert_eq!(secs(&fixed, result.clone()), None);
+
+        assert_eq!(secs(&still(true, None), result.clone()), Some(10 * 60));
+
+        // replays keep their own time until the result
+        let replay = still(false, Some(time::Duration::from_secs(1)));
+        assert_eq!(secs(&replay, MatchStatus::Stumps), Some(1));
+        assert_eq!(secs(&replay, result), None);
+    }
+
+    #[test]
+    fn commentary_merges_pages_newest_first() {
+        let ball = |innings, unique: &str, text: &str| CommentaryBall {
+            innings,
+            overs_unique: unique.to_string(),
+            text: text.to_string(),
+            ..CommentaryBall::default()
+        };
+        let mut commentary = Commentary::default();
+        commentary.merge(&[
+            ball(1, "19.06", "last ball"),
+            ball(2, "0.01", "dot"),
+            ball(2, "0.02", ""),
+        ]);
+        // the next page overlaps, and fills in the text of a ball we'd already seen
+        commentary.merge(&[
+            ball(2, "0.03", "wide"),
+            ball(2, "0.02", "four"),
+            ball(2, "0.04", "six"),
+        ]);
+
+        assert_eq!(commentary.len(), 5);
+        let order: Vec<(u32, &str, &str)> = commentary
+            .newest_first()
+            .map(|b| (b.innings, b.overs_unique.as_str(), b.text.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (2, "0.04", "six"),
+                (2, "0.03", "wide"),
+                (2, "0.02", "four"),
+                (2, "0.01", "dot"),
+                (1, "19.06", "last ball"),
+            ]
+        );
+    }
+
+    #[test]
+    fn economy_uses_balls_per_over() {
+        let bowler = Bowler::new("Hundred", Overs::parse("4", 5).unwrap(), 0, 30, 1);
+        assert_eq!(bowler.economy(), 7.5);
+    }
+}
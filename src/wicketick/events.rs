@@ -0,0 +1,532 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Batter, Bowler, Innings, SimpleSummary};
+
+// MatchEvent is something that happened between two summaries of a match
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MatchEvent {
+    Wicket {
+        batting_team_id: String,
+        // e.g. 3 for the third wicket
+        wicket: u32,
+        // the team's score when it fell
+        runs: u32,
+        // none when the source hasn't said who yet
+        batter: Option<String>,
+        dismissal: Option<String>,
+    },
+    Boundary {
+        batting_team_id: String,
+        batter: String,
+        // 4 or 6
+        runs: u32,
+    },
+    BatterMilestone {
+        batting_team_id: String,
+        batter: String,
+        // 50, 100, 150...
+        milestone: u32,
+    },
+    FiveWickets {
+        bowling_team_id: String,
+        bowler: String,
+        wickets: u32,
+    },
+    InningsEnd {
+        innings: Innings,
+    },
+    NewBatter {
+        batting_team_id: String,
+        batter: String,
+    },
+    BowlingChange {
+        bowling_team_id: String,
+        bowler: String,
+    },
+    TargetReached {
+        batting_team_id: String,
+    },
+    Result {
+        description: String,
+        winner_team_id: Option<String>,
+        // both sides, the result is about the losers too
+        team_ids: Vec<String>,
+    },
+}
+
+impl MatchEvent {
+    // kind is a short name for the sort of event, e.g. for filtering on
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MatchEvent::Wicket { .. } => "wicket",
+            MatchEvent::Boundary { .. } => "boundary",
+            MatchEvent::BatterMilestone { .. } => "milestone",
+            MatchEvent::FiveWickets { .. } => "five_wickets",
+            MatchEvent::InningsEnd { .. } => "innings_end",
+            MatchEvent::NewBatter { .. } => "new_batter",
+            MatchEvent::BowlingChange { .. } => "bowling_change",
+            MatchEvent::TargetReached { .. } => "target_reached",
+            MatchEvent::Result { .. } => "result",
+        }
+    }
+
+    // team_ids are the teams the event is about, batting side first
+    pub fn team_ids(&self) -> Vec<&str> {
+        match self {
+            MatchEvent::Wicket {
+                batting_team_id, ..
+            }
+            | MatchEvent::Boundary {
+                batting_team_id, ..
+            }
+            | MatchEvent::BatterMilestone {
+                batting_team_id, ..
+            }
+            | MatchEvent::NewBatter {
+                batting_team_id, ..
+            }
+            | MatchEvent::TargetReached { batting_team_id } => vec![batting_team_id],
+            MatchEvent::FiveWickets {
+                bowling_team_id, ..
+            }
+            | MatchEvent::BowlingChange {
+                bowling_team_id, ..
+            } => vec![bowling_team_id],
+            MatchEvent::InningsEnd { innings } => {
+                vec![&innings.batting_team_id, &innings.bowling_team_id]
+            }
+            MatchEvent::Result { team_ids, .. } => team_ids.iter().map(|id| id.as_str()).collect(),
+        }
+    }
+
+    pub fn display(&self) -> String {
+        match self {
+            MatchEvent::Wicket {
+                wicket,
+                runs,
+                batter,
+                dismissal,
+                ..
+            } => match (batter, dismissal) {
+                // the fall of wickets way round, e.g. 3-18 for the third wicket at 18
+                (Some(batter), Some(dismissal)) => {
+                    format!("WICKET! {} {} ({}-{})", batter, dismissal, wicket, runs)
+                }
+                (Some(batter), None) => format!("WICKET! {} out ({}-{})", batter, wicket, runs),
+                _ => format!("WICKET! ({}-{})", wicket, runs),
+            },
+            MatchEvent::Boundary { batter, runs, .. } => match runs {
+                6 => format!("SIX! {}", batter),
+                _ => format!("FOUR! {}", batter),
+            },
+            MatchEvent::BatterMilestone {
+                batter, milestone, ..
+            } => format!("{} reaches {}", batter, milestone),
+            MatchEvent::FiveWickets {
+                bowler, wickets, ..
+            } => format!("{} has {} wickets", bowler, wickets),
+            MatchEvent::InningsEnd { innings } => format!("End of innings {}", innings.display()),
+            MatchEvent::NewBatter { batter, .. } => format!("New batter: {}", batter),
+            MatchEvent::BowlingChange { bowler, .. } => format!("Bowling change: {}", bowler),
+            MatchEvent::TargetReached { .. } => "Target reached".to_string(),
+            MatchEvent::Result { description, .. } => description.clone(),
+        }
+    }
+}
+
+// diff works out what happened between two summaries of the same match
+pub fn diff(previous: &SimpleSummary, current: &SimpleSummary) -> Vec<MatchEvent> {
+    let mut events = vec![];
+    if !previous.same_match(current) {
+        return events;
+    }
+
+    let prev_innings = &previous.current_innings;
+    let innings = &current.current_innings;
+
+    if innings.number > prev_innings.number && prev_innings.number > 0 {
+        // the innings array has the final score, the live one may have stopped short
+        let ended = current
+            .match_summary
+            .innings
+            .iter()
+            .find(|i| i.number == prev_innings.number)
+            .unwrap_or(prev_innings)
+            .clone();
+        events.push(MatchEvent::InningsEnd { innings: ended });
+    }
+
+    // everything else only makes sense within the one innings
+    if innings.number == prev_innings.number {
+        wickets(previous, current, &mut events);
+        batters(previous, current, &mut events);
+        bowlers(previous, current, &mut events);
+
+        if let Some(target) = innings.target {
+            if prev_innings.runs < target && innings.runs >= target {
+                events.push(MatchEvent::TargetReached {
+                    batting_team_id: innings.batting_team_id.clone(),
+                });
+            }
+        }
+    }
+
+    if !previous.status.is_finished() {
+        if let super::MatchStatus::Result {
+            description,
+            winner_team_id,
+            ..
+        } = &current.status
+        {
+            let mut team_ids: Vec<String> = current
+                .match_summary
+                .teams
+                .iter()
+                .map(|t| t.id.clone())
+                .collect();
+            if team_ids.is_empty() {
+                let innings = &current.current_innings;
+                team_ids = vec![
+                    innings.batting_team_id.clone(),
+                    innings.bowling_team_id.clone(),
+                ];
+            }
+            events.push(MatchEvent::Result {
+                description: description.clone(),
+                winner_team_id: winner_team_id.clone(),
+                team_ids,
+            });
+        }
+    }
+
+    events
+}
+
+fn wickets(previous: &SimpleSummary, current: &SimpleSummary, events: &mut Vec<MatchEvent>) {
+    let innings = &current.current_innings;
+    for wicket in previous.current_innings.wickets + 1..=innings.wickets {
+        let fow = current.fall_of_wickets.iter().find(|f| f.wicket == wicket);
+        events.push(MatchEvent::Wicket {
+            batting_team_id: innings.batting_team_id.clone(),
+            wicket,
+            runs: fow.map(|f| f.runs).unwrap_or(innings.runs),
+            batter: fow.map(|f| f.batter.clone()),
+            dismissal: fow.map(|f| f.dismissal.clone()).filter(|d| !d.is_empty()),
+        });
+    }
+}
+
+fn batters(previous: &SimpleSummary, current: &SimpleSummary, events: &mut Vec<MatchEvent>) {
+    let batting_team_id = &current.current_innings.batting_team_id;
+    let previous_batters = previous.active_players.batters();
+    let find = |b: &Batter| {
+        previous_batters
+            .iter()
+            .find(|p| p.name() == b.name())
+            .copied()
+    };
+
+    for batter in current.active_players.batters() {
+        let Some(before) = find(batter) else {
+            // no players at all is just the source not saying, rather than a new pair
+            if !previous_batters.is_empty() {
+                events.push(MatchEvent::NewBatter {
+                    batting_team_id: batting_team_id.clone(),
+                    batter: batter.name().to_string(),
+                });
+            }
+            continue;
+        };
+
+        let boundaries = [
+            (4, batter.fours().saturating_sub(before.fours())),
+            (6, batter.sixes().saturating_sub(before.sixes())),
+        ];
+        for (runs, count) in boundaries {
+            for _ in 0..count {
+                events.push(MatchEvent::Boundary {
+                    batting_team_id: batting_team_id.clone(),
+                    batter: batter.name().to_string(),
+                    runs,
+                });
+            }
+        }
+
+        for milestone in (50..=batter.runs()).step_by(50) {
+            if before.runs() < milestone {
+                events.push(MatchEvent::BatterMilestone {
+                    batting_team_id: batting_team_id.clone(),
+                    batter: batter.name().to_string(),
+                    milestone,
+                });
+            }
+        }
+    }
+}
+
+fn bowlers(previous: &SimpleSummary, current: &SimpleSummary, events: &mut Vec<MatchEvent>) {
+    let bowling_team_id = &current.current_innings.bowling_team_id;
+    let previous_bowlers = previous.active_players.bowlers();
+    let find = |b: &Bowler| {
+        previous_bowlers
+            .iter()
+            .find(|p| p.name() == b.name())
+            .copied()
+    };
+
+    for bowler in current.active_players.bowlers() {
+        match find(bowler) {
+            Some(before) if before.wickets() < 5 && bowler.wickets() >= 5 => {
+                events.push(MatchEvent::FiveWickets {
+                    bowling_team_id: bowling_team_id.clone(),
+                    bowler: bowler.name().to_string(),
+                    wickets: bowler.wickets(),
+                });
+            }
+            Some(_) => {}
+            None if !previous_bowlers.is_empty() => {
+                events.push(MatchEvent::BowlingChange {
+                    bowling_team_id: bowling_team_id.clone(),
+                    bowler: bowler.name().to_string(),
+                });
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::notify::Filter;
+    use crate::wicketick::{FallOfWicket, MatchStatus, Overs, Partnership, Team};
+
+    // summary is a match between WORCS and BEARS with WORCS batting
+    fn summary(runs: u32, wickets: u32, batters: [Option<Batter>; 2]) -> SimpleSummary {
+        let team = |id: &str, abbreviation: &str| Team {
+            id: id.to_string(),
+            abbreviation: abbreviation.to_string(),
+            ..Team::default()
+        };
+        let mut summary = SimpleSummary::default();
+        summary.match_summary.teams = vec![team("1509", "WORCS"), team("1479", "BEARS")];
+        summary.current_innings = Innings {
+            number: 1,
+            batting_team_id: "1509".to_string(),
+            bowling_team_id: "1479".to_string(),
+            runs,
+            wickets,
+            ..Innings::default()
+        };
+        let [one, two] = batters;
+        summary.active_players.batter_one = one;
+        summary.active_players.batter_two = two;
+        summary
+    }
+
+    fn batter(name: &str, runs: u32) -> Option<Batter> {
+        Some(Batter::new(name, runs, runs, 0, 0, false))
+    }
+
+    #[test]
+    fn milestones_crossed_in_one_poll() {
+        let previous = summary(140, 2, [batter("Haynes", 45), batter("Libby", 10)]);
+        let current = summary(200, 2, [batter("Haynes", 104), batter("Libby", 11)]);
+        let milestones: Vec<u32> = diff(&previous, &current)
+            .into_iter()
+            .filter_map(|e| match e {
+                MatchEvent::BatterMilestone { milestone, .. } => Some(milestone),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(milestones, vec![50, 100]);
+    }
+
+    #[test]
+    fn new_batter_only_for_a_new_name() {
+        let previous = summary(18, 2, [batter("Haynes", 14), batter("Libby", 4)]);
+        let same = summary(19, 2, [batter("Haynes", 15), batter("Libby", 4)]);
+        assert!(diff(&previous, &same).is_empty());
+
+        let current = summary(18, 3, [batter("Roderick", 0), batter("Libby", 4)]);
+        let new_batters: Vec<MatchEvent> = diff(&previous, &current)
+            .into_iter()
+            .filter(|e| e.kind() == "new_batter")
+            .collect();
+        assert_eq!(
+            new_batters,
+            vec![MatchEvent::NewBatter {
+                batting_team_id: "1509".to_string(),
+                batter: "Roderick".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn innings_change_ends_the_innings_and_nothing_else() {
+        let previous = summary(187, 6, [batter("Haynes", 60), batter("Libby", 4)]);
+        let mut current = summary(0, 0, [batter("Bethell", 0), batter("Davies", 0)]);
+        current.current_innings.number = 2;
+        current.match_summary.innings = vec![Innings {
+            runs: 190,
+            ..previous.current_innings.clone()
+        }];
+
+        let events = diff(&previous, &current);
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            MatchEvent::InningsEnd { innings } => assert_eq!(innings.runs, 190),
+            other => panic!("expected the innings to end, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn different_match_has_no_events() {
+        let previous = summary(18, 2, [batter("Haynes", 14), batter("Libby", 4)]);
+        let mut current = summary(200, 9, [batter("Root", 100), batter("Brook", 50)]);
+        current.match_summary.teams[1].id = "1".to_string();
+        assert!(diff(&previous, &current).is_empty());
+    }
+
+    #[test]
+    fn wicket_reads_like_the_fall_of_wickets() {
+        let previous = summary(18, 2, [batter("Haynes", 14), batter("Libby", 4)]);
+        let mut current = summary(18, 3, [batter("Libby", 4), None]);
+        let fow = FallOfWicket {
+            wicket: 3,
+            runs: 18,
+            overs: Overs::parse("2.5", 6).unwrap(),
+            batter: "Haynes".to_string(),
+            batter_runs: Some(14),
+            batter_balls: None,
+            dismissal: "b Waite".to_string(),
+            partnership: Partnership {
+                wicket: 3,
+                runs: 1,
+                overs: Overs::parse("0.4", 6).unwrap(),
+                run_rate: 1.5,
+            },
+        };
+        current.fall_of_wickets = vec![fow];
+
+        let events = diff(&previous, &current);
+        assert_eq!(
+            events,
+            vec![MatchEvent::Wicket {
+                batting_team_id: "1509".to_string(),
+                wicket: 3,
+                runs: 18,
+                batter: Some("Haynes".to_string()),
+                dismissal: Some("b Waite".to_string()),
+            }]
+        );
+        assert_eq!(events[0].display(), "WICKET! Haynes b Waite (3-18)");
+    }
+
+    #[test]
+    fn boundaries_from_the_batters_fours_and_sixes() {
+        let before = Batter::new("Haynes", 14, 10, 1, 0, true);
+        let after = Batter::new("Haynes", 24, 12, 2, 1, true);
+        let previous = summary(30, 1, [Some(before), batter("Libby", 4)]);
+        let current = summary(40, 1, [Some(after), batter("Libby", 4)]);
+        let boundary = |runs| MatchEvent::Boundary {
+            batting_team_id: "1509".to_string(),
+            batter: "Haynes".to_string(),
+            runs,
+        };
+        let events = diff(&previous, &current);
+        assert_eq!(events, vec![boundary(4), boundary(6)]);
+        assert_eq!(events[1].display(), "SIX! Haynes");
+    }
+
+    #[test]
+    fn five_wickets_and_bowling_changes() {
+        let bowler =
+            |name: &str, wickets| Some(Bowler::new(name, Overs::default(), wickets, 20, 0));
+        let mut previous = summary(100, 6, [batter("Haynes", 40), batter("Libby", 4)]);
+        previous.active_players.bowler_one = bowler("Waite", 4);
+        previous.active_players.bowler_two = bowler("Pennington", 0);
+        let mut current = previous.clone();
+        current.active_players.bowler_one = bowler("Waite", 5);
+        current.active_players.bowler_two = bowler("Brown", 0);
+        current.current_innings.wickets = 6;
+
+        assert_eq!(
+            diff(&previous, &current),
+            vec![
+                MatchEvent::FiveWickets {
+                    bowling_team_id: "1479".to_string(),
+                    bowler: "Waite".to_string(),
+                    wickets: 5,
+                },
+                MatchEvent::BowlingChange {
+                    bowling_team_id: "1479".to_string(),
+                    bowler: "Brown".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn target_reached_once() {
+        let mut previous = summary(185, 4, [batter("Haynes", 60), batter("Libby", 4)]);
+        previous.current_innings.target = Some(188);
+        let mut current = summary(189, 4, [batter("Haynes", 64), batter("Libby", 4)]);
+        current.current_innings.target = Some(188);
+        assert_eq!(
+            diff(&previous, &current),
+            vec![MatchEvent::TargetReached {
+                batting_team_id: "1509".to_string(),
+            }]
+        );
+
+        let mut later = current.clone();
+        later.current_innings.runs = 190;
+        assert!(diff(&current, &later).is_empty());
+    }
+
+    #[test]
+    fn result_once_the_match_is_over() {
+        let previous = summary(187, 6, [batter("Haynes", 60), batter("Libby", 4)]);
+        let mut current = previous.clone();
+        current.status = MatchStatus::Result {
+            description: "Worcs won by 18 runs".to_string(),
+            winner_team_id: Some("1509".to_string()),
+            margin: Some("18 runs".to_string()),
+        };
+        assert_eq!(
+            diff(&previous, &current),
+            vec![MatchEvent::Result {
+                description: "Worcs won by 18 runs".to_string(),
+                winner_team_id: Some("1509".to_string()),
+                team_ids: vec!["1509".to_string(), "1479".to_string()],
+            }]
+        );
+        assert!(diff(&current, &current).is_empty());
+    }
+    #[test]
+    fn result_reaches_the_losers_and_a_draw_too() {
+        let worcs = Filter {
+            teams: vec!["Worcs".to_string()],
+            ..Filter::default()
+        };
+        let previous = summary(187, 6, [batter("Haynes", 60), batter("Libby", 4)]);
+        let results = [
+            (Some("1479".to_string()), "Bears won by 4 wickets"),
+            (None, "Match drawn"),
+        ];
+        for (winner_team_id, description) in results {
+            let mut current = previous.clone();
+            current.status = MatchStatus::Result {
+                description: description.to_string(),
+                winner_team_id,
+                margin: None,
+            };
+            let events = diff(&previous, &current);
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].kind(), "result");
+            assert!(worcs.matches(&events[0], &current));
+        }
+    }
+}